@@ -2,8 +2,13 @@ extern crate embed_resource;
 
 use bevy_ldtk_scene::prelude::*;
 use bevy_ldtk_scene::world::ExtractLdtkWorld;
+use serde_json::Value;
 use std::env;
-use std::path::PathBuf;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+const LDTK_PATH: &str = "assets/ldtk/time-marches-on.ldtk";
+const LICENSES_DIR: &str = "credits/licenses";
 
 fn main() {
     let target = env::var("TARGET").unwrap();
@@ -12,10 +17,10 @@ fn main() {
         embed_resource::compile("build/windows/icon.rc");
     }
 
-    println!("cargo::rerun-if-changed=assets/ldtk/time-marches-on.ldtk");
+    println!("cargo::rerun-if-changed={LDTK_PATH}");
 
     let out_dir = std::env::var("OUT_DIR").unwrap();
-    let world = ExtractLdtkWorld::new("assets/ldtk/time-marches-on.ldtk")
+    let world = ExtractLdtkWorld::new(LDTK_PATH)
         .unwrap()
         .extract_with((
             ExtractComposites,
@@ -27,10 +32,186 @@ fn main() {
             ExtractLevelUids,
         ))
         .unwrap()
-        .write(PathBuf::new().join(out_dir).join("world.rs"))
+        .write(PathBuf::new().join(&out_dir).join("world.rs"))
         .unwrap()
         .write(PathBuf::new().join("ldtk_out.rs"))
         .unwrap()
         .world();
     world.save("assets/ldtk/time-marches-on.ron").unwrap();
+
+    let project: Value =
+        serde_json::from_str(&std::fs::read_to_string(LDTK_PATH).unwrap()).unwrap();
+    write_doors(&project, &Path::new(&out_dir).join("doors.rs"));
+    write_levels(&project, &Path::new(&out_dir).join("levels.rs"));
+
+    println!("cargo::rerun-if-changed={LICENSES_DIR}");
+    write_licenses(&Path::new(&out_dir).join("licenses.rs"));
+}
+
+/// Implement `LdtkDoor` for every entity with a `Target` point field.
+///
+/// Doors tagged `side_door` use the interactable `SideDoor` shape. Doors
+/// with nullable `x` and `y` fields and a `load` field can point into other
+/// levels. A `key` field names the item that unlocks the door, and a
+/// `locked` field sets the message shown while it's locked.
+fn write_doors(project: &Value, path: &Path) {
+    let mut impls = String::new();
+    let mut registrations = String::new();
+
+    for entity in project["defs"]["entities"].as_array().unwrap() {
+        let field_defs = entity["fieldDefs"].as_array().unwrap();
+        let fields: Vec<&str> = field_defs
+            .iter()
+            .filter_map(|field| field["identifier"].as_str())
+            .collect();
+
+        if !fields.contains(&"Target") {
+            continue;
+        }
+
+        let name = entity["identifier"].as_str().unwrap();
+        let side = entity["tags"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|tag| tag == "side_door");
+        let shape = if side { "SideDoor" } else { "VerticalDoor" };
+
+        let mut door = String::from("Door::to(self.target.map(DoorDestination::Tile)");
+        if fields.contains(&"x") && fields.contains(&"y") {
+            for field in field_defs
+                .iter()
+                .filter(|field| field["identifier"] == "x" || field["identifier"] == "y")
+            {
+                assert!(
+                    field["canBeNull"].as_bool().unwrap(),
+                    "`{name}.{}` should be nullable",
+                    field["identifier"].as_str().unwrap(),
+                );
+            }
+
+            door.push_str(
+                ".or_else(|| self.x.zip(self.y)\
+                 .map(|(x, y)| DoorDestination::Position(Vec2::new(x, -y))))",
+            );
+        }
+        door.push(')');
+        if fields.contains(&"load") {
+            door.push_str(".load(&self.load)");
+        }
+        if fields.contains(&"key") {
+            door.push_str(".key(&self.key)");
+        }
+        if fields.contains(&"locked") {
+            door.push_str(".locked_message(&self.locked)");
+        }
+
+        writeln!(
+            impls,
+            "impl LdtkDoor for world::{name} {{\n    \
+                 type Shape = {shape};\n\n    \
+                 fn door(&self) -> Door {{\n        {door}\n    }}\n\
+             }}\n"
+        )
+        .unwrap();
+        writeln!(registrations, "    app.register_door::<world::{name}>();").unwrap();
+    }
+
+    let source = format!(
+        "{impls}/// Register every door type in the LDtk project.\n\
+         fn register_ldtk_doors(app: &mut App) {{\n{registrations}}}\n"
+    );
+    std::fs::write(path, source).unwrap();
+}
+
+/// The world space bounds of every level, and where its chapter starts
+/// the player if it has a `spawn` field, keyed by level identifier.
+fn write_levels(project: &Value, path: &Path) {
+    let grid = project["defaultGridSize"].as_f64().unwrap();
+    let mut bounds = String::new();
+    let mut spawns = String::new();
+
+    for level in project["levels"].as_array().unwrap() {
+        let name = level["identifier"].as_str().unwrap();
+        let x = level["worldX"].as_f64().unwrap();
+        let y = level["worldY"].as_f64().unwrap();
+        let width = level["pxWid"].as_f64().unwrap();
+        let height = level["pxHei"].as_f64().unwrap();
+
+        writeln!(
+            bounds,
+            "    ({name:?}, Rect {{ min: Vec2::new({:?}, {:?}), max: Vec2::new({:?}, {:?}) }}),",
+            x,
+            -y - height,
+            x + width,
+            -y,
+        )
+        .unwrap();
+
+        let spawn = level["fieldInstances"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|field| field["__identifier"] == "spawn")
+            .map(|field| &field["__value"])
+            .filter(|value| !value.is_null());
+
+        if let Some(spawn) = spawn {
+            let cx = spawn["cx"].as_f64().unwrap();
+            let cy = spawn["cy"].as_f64().unwrap();
+
+            // The center of the spawn tile.
+            writeln!(
+                spawns,
+                "    ({name:?}, Vec2::new({:?}, {:?})),",
+                x + (cx + 0.5) * grid,
+                -(y + (cy + 0.5) * grid),
+            )
+            .unwrap();
+        }
+    }
+
+    let source = format!(
+        "const LEVEL_BOUNDS: &[(&str, Rect)] = &[\n{bounds}];\n\n\
+         const LEVEL_SPAWNS: &[(&str, Vec2)] = &[\n{spawns}];\n"
+    );
+    std::fs::write(path, source).unwrap();
+}
+
+/// Include every license in `credits/licenses`, named after its file.
+///
+/// `bevy_game_template.md` covers the "Bevy game template", for example.
+fn write_licenses(path: &Path) {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let mut files: Vec<PathBuf> = std::fs::read_dir(Path::new(&manifest_dir).join(LICENSES_DIR))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "md"))
+        .collect();
+    files.sort();
+
+    let mut licenses = String::new();
+    for file in files {
+        let stem = file
+            .file_stem()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .replace('_', " ");
+        let mut chars = stem.chars();
+        let project: String = chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect())
+            .unwrap_or_default();
+
+        writeln!(
+            licenses,
+            "    ({project:?}, include_str!({:?})),",
+            file.display().to_string()
+        )
+        .unwrap();
+    }
+
+    let source = format!("const LICENSES: &[(&str, &str)] = &[\n{licenses}];\n");
+    std::fs::write(path, source).unwrap();
 }
@@ -2,6 +2,9 @@ use bevy::prelude::*;
 use bevy_seedling::prelude::*;
 use bevy_tween::{BevyTweenRegisterSystems, component_dyn_tween_system, component_tween_system};
 
+use crate::reset::ResetAppExt;
+
+pub mod null;
 pub mod tween;
 
 pub struct AudioPlugin;
@@ -14,8 +17,10 @@ impl Plugin for AudioPlugin {
              server: Res<AssetServer>,
              default_pool: Single<Entity, With<SamplerPool<DefaultPool>>>,
              mut scale: ResMut<DefaultSpatialScale>| {
-                // Create the SFX bus.
+                // Create the SFX, music, and dialogue glyph buses.
                 commands.spawn((SfxBus, VolumeNode::default()));
+                commands.spawn((MusicBus, VolumeNode::default()));
+                commands.spawn((GlyphBus, VolumeNode::default()));
 
                 // Re-route the default pool to the SFX bus.
                 commands
@@ -33,10 +38,14 @@ impl Plugin for AudioPlugin {
                     ))
                     .connect(SfxBus);
 
-                commands.spawn((
-                    SamplerPool(MusicPool),
-                    sample_effects![VolumeNode::default()],
-                ));
+                commands
+                    .spawn((
+                        SamplerPool(MusicPool),
+                        sample_effects![VolumeNode::default()],
+                    ))
+                    .connect(MusicBus);
+
+                commands.spawn(SamplerPool(GlyphPool)).connect(GlyphBus);
 
                 // commands.spawn((
                 //     MusicPool,
@@ -47,6 +56,8 @@ impl Plugin for AudioPlugin {
                 // ));
             },
         )
+        .on_reset(despawn_gameplay_sounds)
+        .on_reset(reset_music_volume)
         .add_tween_systems((
             component_tween_system::<tween::InterpolateSampleSpeed>(),
             component_dyn_tween_system::<PlaybackSettings>(),
@@ -58,6 +69,25 @@ impl Plugin for AudioPlugin {
     }
 }
 
+/// Stop the music and the spatial sounds placed around the levels.
+///
+/// Menu and UI sounds play through the default pool, so they carry on.
+fn despawn_gameplay_sounds(
+    players: Query<Entity, (With<SamplePlayer>, Or<(With<MusicPool>, With<SpatialPool>)>)>,
+    mut commands: Commands,
+) {
+    for player in players.iter() {
+        commands.entity(player).despawn();
+    }
+}
+
+/// Music fades leave the pool's volume wherever they stopped.
+fn reset_music_volume(mut volume: Query<&mut VolumeNode, With<SamplerPool<MusicPool>>>) {
+    for mut volume in volume.iter_mut() {
+        volume.volume = Volume::Decibels(0.0);
+    }
+}
+
 /// A pool for all spatial sounds.
 #[derive(PoolLabel, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct SpatialPool;
@@ -66,6 +96,18 @@ pub struct SpatialPool;
 #[derive(PoolLabel, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct MusicPool;
 
+/// A pool for the blips played as dialogue is revealed.
+#[derive(PoolLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GlyphPool;
+
 /// Volume node through which all sound effects are routed.
 #[derive(NodeLabel, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct SfxBus;
+
+/// Volume node through which the music pool is routed.
+#[derive(NodeLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MusicBus;
+
+/// Volume node through which dialogue glyphs are routed.
+#[derive(NodeLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GlyphBus;
@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+use bevy_seedling::prelude::*;
+
+use super::{MusicPool, SpatialPool};
+
+/// Set this variable to run without a sound device.
+const NULL_AUDIO_VAR: &str = "TIME_MARCHES_ON_NULL_AUDIO";
+
+/// Whether to use [`NullAudioPlugin`] instead of a real output stream.
+///
+/// Enabled by the `null-audio` feature or the `TIME_MARCHES_ON_NULL_AUDIO`
+/// environment variable.
+pub fn requested() -> bool {
+    cfg!(feature = "null-audio") || std::env::var_os(NULL_AUDIO_VAR).is_some()
+}
+
+/// A silent stand-in for `SeedlingPlugin`.
+///
+/// Pools, buses, and audio tweens are set up as usual, but nothing is sent
+/// to a sound device. Every [`SamplePlayer`] is recorded in the [`AudioLog`]
+/// and then completes on the next frame, following its
+/// [`PlaybackSettings::on_complete`].
+pub struct NullAudioPlugin;
+
+impl Plugin for NullAudioPlugin {
+    fn build(&self, app: &mut App) {
+        info!("audio output disabled");
+
+        app.init_resource::<AudioLog>()
+            .init_resource::<DefaultSpatialScale>()
+            .add_systems(PreStartup, |mut commands: Commands| {
+                commands.spawn(SamplerPool(DefaultPool));
+            })
+            .add_systems(Last, complete_samples)
+            .add_observer(record_sample);
+    }
+}
+
+/// Which pool a sample was played through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pool {
+    Default,
+    Spatial,
+    Music,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlayedSample {
+    /// The sample's asset path, such as `audio/sfx/door-open.wav`.
+    pub path: String,
+    pub volume: Volume,
+    pub pool: Pool,
+}
+
+/// Every sample played while audio output is disabled, oldest first.
+#[derive(Debug, Default, Resource)]
+pub struct AudioLog(pub Vec<PlayedSample>);
+
+#[allow(unused)]
+impl AudioLog {
+    /// Whether the sample at `path` has been played.
+    pub fn played(&self, path: &str) -> bool {
+        self.0.iter().any(|sample| sample.path == path)
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+/// Marks a sample that has been recorded and will complete next frame.
+#[derive(Component)]
+struct Recorded;
+
+fn record_sample(
+    trigger: Trigger<OnAdd, SamplePlayer>,
+    samples: Query<(&SamplePlayer, Has<SpatialPool>, Has<MusicPool>)>,
+    mut log: ResMut<AudioLog>,
+    mut commands: Commands,
+) {
+    let Ok((sample, spatial, music)) = samples.get(trigger.target()) else {
+        return;
+    };
+
+    let path = sample
+        .sample
+        .path()
+        .map(|path| path.to_string())
+        .unwrap_or_default();
+    debug!("playing `{path}`");
+
+    log.0.push(PlayedSample {
+        path,
+        volume: sample.volume,
+        pool: match (spatial, music) {
+            (true, _) => Pool::Spatial,
+            (_, true) => Pool::Music,
+            _ => Pool::Default,
+        },
+    });
+
+    commands.entity(trigger.target()).insert(Recorded);
+}
+
+fn complete_samples(
+    samples: Query<(Entity, Option<&PlaybackSettings>), With<Recorded>>,
+    mut commands: Commands,
+) {
+    for (entity, settings) in samples.iter() {
+        match settings.map(|settings| &settings.on_complete) {
+            Some(OnComplete::Remove) => {
+                commands
+                    .entity(entity)
+                    .remove::<(SamplePlayer, PlaybackSettings, Recorded)>();
+            }
+            Some(OnComplete::Despawn) | None => {
+                commands.entity(entity).despawn();
+            }
+            Some(_) => {
+                commands.entity(entity).remove::<Recorded>();
+            }
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::text::TextBounds;
+use bevy_enhanced_input::prelude::*;
+use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
+
+use crate::controls::{Control, Controls};
+use crate::reset::ResetAppExt;
+use crate::textbox::{OpenBacklog, Speaker, Textbox, TextboxContext};
+
+pub struct BacklogPlugin;
+
+impl Plugin for BacklogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_input_context::<BacklogContext>()
+            .init_resource::<DialogueHistory>()
+            .reset_resource::<DialogueHistory>()
+            .despawn_on_reset::<Backlog>()
+            .add_systems(Update, show_backlog)
+            .add_observer(bind)
+            .add_observer(open_backlog)
+            .add_observer(close_backlog)
+            .add_observer(scroll_backlog);
+    }
+}
+
+/// The most lines kept in the [`DialogueHistory`].
+const HISTORY_LEN: usize = 200;
+
+/// How many lines the backlog shows at once.
+const PAGE_LEN: usize = 6;
+
+/// Every blurb shown in the textbox, oldest first.
+#[derive(Debug, Default, Resource)]
+pub struct DialogueHistory {
+    entries: VecDeque<HistoryEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct HistoryEntry {
+    pub speaker: Speaker,
+    pub text: String,
+}
+
+impl DialogueHistory {
+    pub fn push(&mut self, speaker: Speaker, text: impl Into<String>) {
+        if self.entries.len() == HISTORY_LEN {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(HistoryEntry {
+            speaker,
+            text: text.into(),
+        });
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &HistoryEntry> {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[derive(InputContext)]
+pub struct BacklogContext;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool, require_reset = true)]
+struct CloseBacklog;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = Vec2, require_reset = true)]
+struct ScrollBacklog;
+
+fn bind(
+    trigger: Trigger<Binding<BacklogContext>>,
+    mut actions: Query<&mut Actions<BacklogContext>>,
+    controls: Res<Controls>,
+) -> Result {
+    let mut actions = actions.get_mut(trigger.target())?;
+
+    actions
+        .bind::<CloseBacklog>()
+        .to(controls.inputs(Control::CloseBacklog))
+        .with_conditions(JustPress::default());
+
+    actions
+        .bind::<ScrollBacklog>()
+        .to((
+            Cardinal::wasd_keys(),
+            Cardinal::arrow_keys(),
+            Cardinal::dpad_buttons(),
+        ))
+        .with_conditions(JustPress::default());
+
+    Ok(())
+}
+
+/// The open backlog overlay, scrolled `scroll` lines up from the most recent.
+#[derive(Component)]
+struct Backlog {
+    scroll: usize,
+}
+
+#[derive(Component)]
+struct BacklogText;
+
+/// Open the backlog over the textbox, taking its input until closed.
+fn open_backlog(
+    trigger: Trigger<Fired<OpenBacklog>>,
+    existing: Query<(), With<Backlog>>,
+    server: Res<AssetServer>,
+    mut commands: Commands,
+) {
+    if !existing.is_empty() {
+        return;
+    }
+
+    commands
+        .entity(trigger.target())
+        .remove::<Actions<TextboxContext>>();
+
+    let bounds = Vec2::new(
+        crate::WIDTH * crate::RESOLUTION_SCALE - 160.,
+        crate::HEIGHT * crate::RESOLUTION_SCALE - 120.,
+    );
+
+    commands.spawn((
+        Backlog { scroll: 0 },
+        Actions::<BacklogContext>::default(),
+        HIGH_RES_LAYER,
+        Sprite::from_color(
+            Color::BLACK.with_alpha(0.9),
+            Vec2::new(crate::WIDTH, crate::HEIGHT) * crate::RESOLUTION_SCALE,
+        ),
+        Transform::from_xyz(0., 0., 950.),
+        children![(
+            BacklogText,
+            Text2d::default(),
+            TextFont {
+                font: server.load("fonts/raster-forge.ttf"),
+                font_size: 42.,
+                ..Default::default()
+            },
+            TextBounds::new(bounds.x, bounds.y),
+            Transform::from_xyz(0., bounds.y / 2., 1.),
+            Anchor::TopCenter,
+            HIGH_RES_LAYER,
+        )],
+    ));
+}
+
+fn close_backlog(
+    trigger: Trigger<Fired<CloseBacklog>>,
+    textbox: Option<Single<Entity, With<Textbox>>>,
+    mut commands: Commands,
+) {
+    commands.entity(trigger.target()).despawn();
+
+    if let Some(textbox) = textbox {
+        commands
+            .entity(*textbox)
+            .insert(Actions::<TextboxContext>::default());
+    }
+}
+
+fn scroll_backlog(
+    trigger: Trigger<Fired<ScrollBacklog>>,
+    mut backlog: Single<&mut Backlog>,
+    history: Res<DialogueHistory>,
+) {
+    let max = history.len().saturating_sub(PAGE_LEN);
+
+    if trigger.value.y > 0.0 {
+        backlog.scroll = (backlog.scroll + 1).min(max);
+    } else if trigger.value.y < 0.0 {
+        backlog.scroll = backlog.scroll.saturating_sub(1);
+    }
+}
+
+fn show_backlog(
+    backlog: Option<Single<&Backlog, Changed<Backlog>>>,
+    text: Option<Single<Entity, With<BacklogText>>>,
+    history: Res<DialogueHistory>,
+    server: Res<AssetServer>,
+    mut commands: Commands,
+) {
+    let (Some(backlog), Some(text)) = (backlog, text) else {
+        return;
+    };
+
+    let font = server.load("fonts/raster-forge.ttf");
+    let end = history.len() - backlog.scroll.min(history.len());
+    let start = end.saturating_sub(PAGE_LEN);
+
+    let mut text = commands.entity(*text);
+    text.despawn_related::<Children>();
+
+    if history.is_empty() {
+        text.insert(Text2d::new("Nothing has been said yet."));
+        return;
+    }
+
+    text.insert(Text2d::default());
+    text.with_children(|spans| {
+        for entry in history.iter().skip(start).take(end - start) {
+            if let Some(name) = entry.speaker.name() {
+                spans.spawn((
+                    TextSpan::new(format!("{name}: ")),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 42.,
+                        ..Default::default()
+                    },
+                    TextColor(SPEAKER_COLOR),
+                ));
+            }
+
+            spans.spawn((
+                TextSpan::new(format!("{}\n\n", entry.text)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 42.,
+                    ..Default::default()
+                },
+            ));
+        }
+    });
+}
+
+const SPEAKER_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
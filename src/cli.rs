@@ -0,0 +1,334 @@
+//! Command-line options for jumping straight to a scene during development.
+//!
+//! ```text
+//! time-marches-on --level Level2 --flag tea_done --give-item Key --mute
+//! time-marches-on --level Level1 --cutscene tea
+//! ```
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::cutscenes;
+use crate::flags::StoryFlags;
+use crate::inventory::item::{self, Inventory};
+use crate::levels::LevelId;
+use crate::levels::chapter::enter_chapter;
+use crate::sequence::run_after;
+use crate::{GameRng, GameState};
+
+const USAGE: &str = "\
+Usage: time-marches-on [OPTIONS]
+
+Options:
+  --level <LEVEL>        Start at the beginning of Level0, Level1 or Level2
+  --skip-intro           Skip the title screen and intro, starting a new game
+  --mute                 Silence all audio for this session
+  --flag <NAME[=VALUE]>  Set a story flag, to 1 if no value is given
+  --give-item <NAME>     Put an item in the inventory, like Key or Pills
+  --cutscene <NAME>      Play a cutscene once the level loads, like tea
+  --seed <N>             Seed the game's random number generator
+  --windowed <WxH>       Open a window of the given size, like 1280x720
+  -h, --help             Print this message";
+
+pub struct CliPlugin;
+
+impl Plugin for CliPlugin {
+    fn build(&self, app: &mut App) {
+        let options = app
+            .world_mut()
+            .get_resource_or_init::<LaunchOptions>()
+            .clone();
+
+        if let Some(seed) = options.seed {
+            app.insert_resource(GameRng::from_seed(seed));
+        }
+
+        if options.starts_directly() {
+            app.add_systems(
+                OnExit(GameState::Loading),
+                (start_directly, play_cutscene).chain(),
+            );
+        }
+    }
+}
+
+/// How the game was asked to start.
+#[derive(Debug, Default, Clone, PartialEq, Resource)]
+pub struct LaunchOptions {
+    pub level: Option<LevelId>,
+    pub skip_intro: bool,
+    pub mute: bool,
+    pub flags: Vec<(String, i64)>,
+    pub items: Vec<String>,
+    pub cutscene: Option<String>,
+    pub seed: Option<u64>,
+    pub windowed: Option<UVec2>,
+}
+
+impl LaunchOptions {
+    /// Parse the process arguments, exiting with usage on `--help` or an
+    /// invalid option.
+    pub fn from_env() -> Self {
+        match Self::parse(std::env::args().skip(1)) {
+            Ok(Some(options)) => options,
+            Ok(None) => {
+                println!("{USAGE}");
+                std::process::exit(0);
+            }
+            Err(e) => {
+                eprintln!("error: {e}\n\n{USAGE}");
+                std::process::exit(2);
+            }
+        }
+    }
+
+    /// Parse `args`, not including the program name.
+    ///
+    /// Returns `None` if help was requested.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .ok_or_else(|| format!("`{name}` expects a value"))
+            };
+
+            match arg.as_str() {
+                "--level" => {
+                    let level = value("--level")?;
+                    options.level = Some(parse_level(&level)?);
+                }
+                "--skip-intro" => options.skip_intro = true,
+                "--mute" => options.mute = true,
+                "--flag" => {
+                    let flag = value("--flag")?;
+                    options.flags.push(parse_flag(&flag)?);
+                }
+                "--give-item" => {
+                    let name = value("--give-item")?;
+                    options.items.push(parse_item(&name)?);
+                }
+                "--cutscene" => {
+                    let name = value("--cutscene")?;
+                    options.cutscene = Some(parse_cutscene(&name)?);
+                }
+                "--seed" => {
+                    let seed = value("--seed")?;
+                    options.seed =
+                        Some(seed.parse().map_err(|_| format!("invalid seed `{seed}`"))?);
+                }
+                "--windowed" => {
+                    let size = value("--windowed")?;
+                    options.windowed = Some(parse_size(&size)?);
+                }
+                "-h" | "--help" => return Ok(None),
+                _ => return Err(format!("unknown option `{arg}`")),
+            }
+        }
+
+        Ok(Some(options))
+    }
+
+    /// Whether to go straight from loading into the game, skipping the
+    /// title screen and intro.
+    pub fn starts_directly(&self) -> bool {
+        self.skip_intro || self.level.is_some() || self.cutscene.is_some()
+    }
+}
+
+fn parse_level(level: &str) -> Result<LevelId, String> {
+    LevelId::ALL
+        .into_iter()
+        .find(|id| format!("{id:?}").eq_ignore_ascii_case(level))
+        .or_else(|| LevelId::from_name(level))
+        .ok_or_else(|| format!("unknown level `{level}`"))
+}
+
+fn parse_flag(flag: &str) -> Result<(String, i64), String> {
+    match flag.split_once('=') {
+        Some((name, value)) => value
+            .parse()
+            .map(|value| (name.to_string(), value))
+            .map_err(|_| format!("invalid value for flag `{name}`: `{value}`")),
+        None => Ok((flag.to_string(), 1)),
+    }
+}
+
+fn parse_item(name: &str) -> Result<String, String> {
+    item::item(name)
+        .map(|item| item.name)
+        .ok_or_else(|| format!("unknown item `{name}`"))
+}
+
+fn parse_cutscene(name: &str) -> Result<String, String> {
+    cutscenes::NAMES
+        .iter()
+        .find(|cutscene| cutscene.eq_ignore_ascii_case(name))
+        .map(|cutscene| cutscene.to_string())
+        .ok_or_else(|| format!("unknown cutscene `{name}`"))
+}
+
+fn parse_size(size: &str) -> Result<UVec2, String> {
+    size.split_once('x')
+        .and_then(|(w, h)| Some(UVec2::new(w.parse().ok()?, h.parse().ok()?)))
+        .filter(|size| size.x > 0 && size.y > 0)
+        .ok_or_else(|| format!("invalid window size `{size}`, expected WxH"))
+}
+
+/// Set up the chosen chapter along with any extra flags and items.
+fn start_directly(world: &mut World) {
+    let options = world.resource::<LaunchOptions>().clone();
+    let level = options.level.unwrap_or(LevelId::Level0);
+    info!("starting directly in {level:?}");
+
+    enter_chapter(world, level);
+
+    let mut flags = world.resource_mut::<StoryFlags>();
+    for (name, value) in options.flags.iter() {
+        flags.set_value(name, *value);
+    }
+
+    let Ok(inventory) = world
+        .query_filtered::<Entity, With<Inventory>>()
+        .single(world)
+    else {
+        error!("no inventory to give items to");
+        return;
+    };
+
+    for item in options.items.iter().filter_map(|name| item::item(name)) {
+        world.spawn((item, ChildOf(inventory)));
+    }
+}
+
+/// Give the level a moment to spawn in before playing the chosen cutscene.
+fn play_cutscene(options: Res<LaunchOptions>, mut commands: Commands) {
+    let Some(name) = options.cutscene.clone() else {
+        return;
+    };
+
+    run_after(
+        Duration::from_secs(2),
+        move |mut commands: Commands| {
+            if !cutscenes::play(&name, &mut commands) {
+                error!("no cutscene called `{name}`");
+            }
+        },
+        &mut commands,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<LaunchOptions>, String> {
+        LaunchOptions::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn options(args: &[&str]) -> LaunchOptions {
+        parse(args).unwrap().unwrap()
+    }
+
+    #[test]
+    fn no_arguments_start_normally() {
+        let options = options(&[]);
+        assert_eq!(options, LaunchOptions::default());
+        assert!(!options.starts_directly());
+    }
+
+    #[test]
+    fn parses_level() {
+        let options = options(&["--level", "level2"]);
+        assert_eq!(options.level, Some(LevelId::Level2));
+        assert!(options.starts_directly());
+
+        assert!(parse(&["--level", "Level9"]).is_err());
+    }
+
+    #[test]
+    fn parses_switches() {
+        let options = options(&["--skip-intro", "--mute"]);
+        assert!(options.skip_intro);
+        assert!(options.mute);
+        assert!(options.starts_directly());
+    }
+
+    #[test]
+    fn parses_flags() {
+        let options = options(&["--flag", "tea_done", "--flag", "pills=2"]);
+        assert_eq!(
+            options.flags,
+            vec![("tea_done".to_string(), 1), ("pills".to_string(), 2)]
+        );
+
+        assert!(parse(&["--flag", "pills=lots"]).is_err());
+    }
+
+    #[test]
+    fn gives_real_items() {
+        let options = options(&["--give-item", "key", "--give-item", "Pills"]);
+        assert_eq!(options.items, vec![item::KEY, item::PILLS]);
+
+        assert!(parse(&["--give-item", "Sandwich"]).is_err());
+    }
+
+    #[test]
+    fn parses_cutscene() {
+        let options = options(&["--cutscene", "Tea"]);
+        assert_eq!(options.cutscene.as_deref(), Some("tea"));
+        assert!(options.starts_directly());
+
+        assert!(parse(&["--cutscene", "credits"]).is_err());
+    }
+
+    #[test]
+    fn parses_seed() {
+        assert_eq!(options(&["--seed", "42"]).seed, Some(42));
+        assert!(parse(&["--seed", "-1"]).is_err());
+        assert!(parse(&["--seed", "forty"]).is_err());
+    }
+
+    #[test]
+    fn parses_window_size() {
+        assert_eq!(
+            options(&["--windowed", "1280x720"]).windowed,
+            Some(UVec2::new(1280, 720))
+        );
+
+        for size in ["0x720", "1280x0", "1280", "abc", "1280x", "x720", "-1x720"] {
+            assert!(parse(&["--windowed", size]).is_err(), "{size}");
+        }
+    }
+
+    #[test]
+    fn missing_values_are_errors() {
+        for option in [
+            "--level",
+            "--flag",
+            "--give-item",
+            "--cutscene",
+            "--seed",
+            "--windowed",
+        ] {
+            assert_eq!(parse(&[option]), Err(format!("`{option}` expects a value")),);
+        }
+    }
+
+    #[test]
+    fn help_stops_parsing() {
+        assert_eq!(parse(&["-h"]), Ok(None));
+        assert_eq!(parse(&["--help", "--bogus"]), Ok(None));
+    }
+
+    #[test]
+    fn rejects_unknown_options() {
+        assert_eq!(
+            parse(&["--fast"]),
+            Err("unknown option `--fast`".to_string())
+        );
+    }
+}
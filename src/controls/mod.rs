@@ -0,0 +1,382 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::save::{data_path, read_data, write_data};
+
+mod screen;
+
+pub use screen::OpenControls;
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Controls::load())
+            .add_plugins(screen::ControlsScreenPlugin)
+            .add_systems(
+                PreUpdate,
+                rebuild_bindings.run_if(resource_changed::<Controls>),
+            );
+    }
+}
+
+/// The controls file, in the game's data directory.
+const CONTROLS_FILE: &str = "controls.ron";
+
+/// The input contexts with rebindable actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ControlContext {
+    Player,
+    Textbox,
+    Backlog,
+    Menu,
+    Cutscene,
+}
+
+impl ControlContext {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Player => "Walking",
+            Self::Textbox => "Dialogue",
+            Self::Backlog => "Backlog",
+            Self::Menu => "Menus",
+            Self::Cutscene => "Cutscenes",
+        }
+    }
+
+    /// Whether actions in both contexts can be listening at once.
+    ///
+    /// The cutscene controls are always active, while the textbox hands its
+    /// input over to the backlog and the player is inhibited by the textbox
+    /// and menus.
+    pub fn active_with(&self, other: &Self) -> bool {
+        self == other || *self == Self::Cutscene || *other == Self::Cutscene
+    }
+}
+
+/// A rebindable action.
+///
+/// Movement and menu navigation always use the directional presets,
+/// so only button actions are listed here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Control {
+    Interact,
+    Pause,
+    Advance,
+    OpenBacklog,
+    CloseBacklog,
+    Select,
+    Unpause,
+    SkipCutscene,
+    FastForward,
+}
+
+impl Control {
+    pub const ALL: [Self; 9] = [
+        Self::Interact,
+        Self::Pause,
+        Self::Advance,
+        Self::OpenBacklog,
+        Self::CloseBacklog,
+        Self::Select,
+        Self::Unpause,
+        Self::SkipCutscene,
+        Self::FastForward,
+    ];
+
+    pub fn context(&self) -> ControlContext {
+        match self {
+            Self::Interact | Self::Pause => ControlContext::Player,
+            Self::Advance | Self::OpenBacklog => ControlContext::Textbox,
+            Self::CloseBacklog => ControlContext::Backlog,
+            Self::Select | Self::Unpause => ControlContext::Menu,
+            Self::SkipCutscene | Self::FastForward => ControlContext::Cutscene,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Interact => "Interact",
+            Self::Pause => "Pause",
+            Self::Advance => "Advance",
+            Self::OpenBacklog => "Open backlog",
+            Self::CloseBacklog => "Close backlog",
+            Self::Select => "Select",
+            Self::Unpause => "Unpause",
+            Self::SkipCutscene => "Skip (hold)",
+            Self::FastForward => "Fast-forward",
+        }
+    }
+
+    fn defaults(&self) -> Vec<ControlInput> {
+        use ControlInput::{Gamepad, Key};
+
+        match self {
+            Self::Interact => vec![
+                Key(KeyCode::KeyJ),
+                Key(KeyCode::KeyE),
+                Key(KeyCode::Space),
+                Gamepad(GamepadButton::South),
+            ],
+            Self::Pause => vec![
+                Key(KeyCode::Escape),
+                Key(KeyCode::Tab),
+                Key(KeyCode::KeyI),
+                Gamepad(GamepadButton::Start),
+                Gamepad(GamepadButton::North),
+            ],
+            Self::Advance => vec![
+                Key(KeyCode::KeyJ),
+                Key(KeyCode::Space),
+                Gamepad(GamepadButton::South),
+            ],
+            Self::OpenBacklog => vec![Key(KeyCode::KeyL), Gamepad(GamepadButton::Select)],
+            Self::CloseBacklog => vec![Key(KeyCode::KeyL), Gamepad(GamepadButton::Select)],
+            Self::Select => vec![
+                Key(KeyCode::Enter),
+                Key(KeyCode::Space),
+                Key(KeyCode::KeyE),
+                Key(KeyCode::KeyJ),
+                Gamepad(GamepadButton::South),
+            ],
+            Self::Unpause => vec![
+                Key(KeyCode::Escape),
+                Key(KeyCode::Tab),
+                Key(KeyCode::KeyI),
+                Gamepad(GamepadButton::Start),
+            ],
+            Self::SkipCutscene => vec![Key(KeyCode::KeyX), Gamepad(GamepadButton::East)],
+            Self::FastForward => vec![Key(KeyCode::KeyF), Gamepad(GamepadButton::West)],
+        }
+    }
+}
+
+/// A single key or gamepad button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControlInput {
+    Key(KeyCode),
+    Gamepad(GamepadButton),
+}
+
+impl ControlInput {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Key(key) => {
+                let name = format!("{key:?}");
+                match name
+                    .strip_prefix("Key")
+                    .or_else(|| name.strip_prefix("Digit"))
+                {
+                    Some(short) if !short.is_empty() => short.into(),
+                    _ => name,
+                }
+            }
+            Self::Gamepad(button) => format!("Pad {button:?}"),
+        }
+    }
+
+    fn same_device(&self, other: &Self) -> bool {
+        matches!(
+            (self, other),
+            (Self::Key(_), Self::Key(_)) | (Self::Gamepad(_), Self::Gamepad(_))
+        )
+    }
+}
+
+impl From<ControlInput> for Input {
+    fn from(input: ControlInput) -> Self {
+        match input {
+            ControlInput::Key(key) => key.into(),
+            ControlInput::Gamepad(button) => button.into(),
+        }
+    }
+}
+
+/// The inputs bound to each action in one context.
+pub type BindingTable = BTreeMap<Control, Vec<ControlInput>>;
+
+/// Every rebindable action's inputs, with one table per input context.
+///
+/// Loaded from `controls.ron` at startup, falling back to the defaults
+/// for anything the file leaves out.
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
+pub struct Controls(BTreeMap<ControlContext, BindingTable>);
+
+impl Default for Controls {
+    fn default() -> Self {
+        let mut controls = Self(BTreeMap::new());
+        controls.fill_defaults();
+        controls
+    }
+}
+
+/// Two actions that can be active together and share an input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conflict {
+    pub input: ControlInput,
+    pub control: Control,
+    pub other: Control,
+}
+
+impl Controls {
+    fn load() -> Self {
+        let path = data_path(CONTROLS_FILE);
+        if !path.exists() {
+            return Self::default();
+        }
+
+        let mut controls: Self = match read_data(&path).and_then(|data| Ok(ron::from_str(&data)?)) {
+            Ok(controls) => controls,
+            Err(e) => {
+                error!("failed to read controls from {}: {e}", path.display());
+                return Self::default();
+            }
+        };
+
+        controls.fill_defaults();
+        for conflict in controls.conflicts() {
+            warn!(
+                "{} is bound to both {:?} and {:?}",
+                conflict.input.label(),
+                conflict.control,
+                conflict.other
+            );
+        }
+
+        controls
+    }
+
+    pub fn save(&self) -> Result {
+        let path = data_path(CONTROLS_FILE);
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        write_data(&path, &contents)?;
+        info!("saved controls to {}", path.display());
+
+        Ok(())
+    }
+
+    fn fill_defaults(&mut self) {
+        for control in Control::ALL {
+            self.0
+                .entry(control.context())
+                .or_default()
+                .entry(control)
+                .or_insert_with(|| control.defaults());
+        }
+    }
+
+    fn table(&self, context: ControlContext) -> Option<&BindingTable> {
+        self.0.get(&context)
+    }
+
+    /// The inputs bound to `control`, ready to pass to `bind`.
+    pub fn inputs(&self, control: Control) -> Vec<Input> {
+        self.get(control).iter().copied().map(Input::from).collect()
+    }
+
+    pub fn get(&self, control: Control) -> &[ControlInput] {
+        self.table(control.context())
+            .and_then(|table| table.get(&control))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Every action with its inputs, across all contexts.
+    fn bindings(&self) -> impl Iterator<Item = (Control, &[ControlInput])> {
+        self.0
+            .values()
+            .flatten()
+            .map(|(control, inputs)| (*control, inputs.as_slice()))
+    }
+
+    /// Another action active alongside `control` that's already bound to `input`.
+    pub fn conflict(&self, control: Control, input: ControlInput) -> Option<Control> {
+        self.bindings()
+            .find(|(other, inputs)| {
+                *other != control
+                    && other.context().active_with(&control.context())
+                    && inputs.contains(&input)
+            })
+            .map(|(other, _)| other)
+    }
+
+    /// Every input shared by two actions that can be active together.
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+        for (control, inputs) in self.bindings() {
+            for input in inputs {
+                for (other, _) in self.bindings().filter(|(other, inputs)| {
+                    *other > control
+                        && other.context().active_with(&control.context())
+                        && inputs.contains(input)
+                }) {
+                    conflicts.push(Conflict {
+                        input: *input,
+                        control,
+                        other,
+                    });
+                }
+            }
+        }
+
+        conflicts
+    }
+
+    /// Bind `control` to `input`, replacing its inputs from the same device.
+    ///
+    /// Returns the conflicting action instead if `input` is already taken.
+    pub fn assign(&mut self, control: Control, input: ControlInput) -> Result<(), Control> {
+        if let Some(other) = self.conflict(control, input) {
+            return Err(other);
+        }
+
+        let inputs = self
+            .0
+            .entry(control.context())
+            .or_default()
+            .entry(control)
+            .or_default();
+        inputs.retain(|existing| !existing.same_device(&input));
+        inputs.push(input);
+
+        Ok(())
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+fn rebuild_bindings(mut commands: Commands) {
+    commands.trigger(RebuildBindings);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_have_no_conflicts() {
+        assert_eq!(Controls::default().conflicts(), []);
+    }
+
+    #[test]
+    fn conflicts_span_active_contexts() {
+        let mut controls = Controls::default();
+        let east = ControlInput::Gamepad(GamepadButton::East);
+
+        // The cutscene controls listen alongside the backlog.
+        assert_eq!(
+            controls.assign(Control::CloseBacklog, east),
+            Err(Control::SkipCutscene)
+        );
+
+        // The backlog never listens alongside the player.
+        let l = ControlInput::Key(KeyCode::KeyL);
+        assert_eq!(controls.assign(Control::Interact, l), Ok(()));
+        assert_eq!(controls.conflicts(), []);
+    }
+}
@@ -0,0 +1,329 @@
+use bevy::{
+    input_focus::{InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+use bevy_enhanced_input::prelude::*;
+
+use super::{Control, ControlInput, Controls};
+use crate::inventory::input::InventoryContext;
+use crate::widgets;
+use crate::{GameState, PlayingState};
+
+/// How long to wait for a new input before giving up on a rebind.
+const CAPTURE_SECS: f32 = 5.0;
+
+pub(super) struct ControlsScreenPlugin;
+
+impl Plugin for ControlsScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (capture_input, show_bindings))
+            .add_systems(OnExit(GameState::Menu), close_controls)
+            .add_systems(OnExit(PlayingState::Paused), close_controls)
+            .add_observer(open_controls)
+            .add_observer(click_button);
+    }
+}
+
+/// Open the controls screen over whatever is on screen.
+#[derive(Debug, Default, Event)]
+pub struct OpenControls;
+
+/// The open controls screen.
+#[derive(Component)]
+struct ControlsPanel;
+
+#[derive(Component)]
+enum ControlsButton {
+    Rebind(Control),
+    Reset,
+    Back,
+}
+
+/// Text listing the inputs bound to a control.
+#[derive(Component)]
+struct BindingText(Control);
+
+#[derive(Component)]
+struct ControlsStatus;
+
+/// Waiting for the player to press the new input for `control`.
+#[derive(Component)]
+struct Capture {
+    control: Control,
+    phase: CapturePhase,
+    timer: Timer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CapturePhase {
+    /// Waiting for the press that started the capture to be released.
+    Arming,
+    Listening,
+    /// Waiting for the captured press to be released before handing
+    /// input back to the menu.
+    Releasing,
+}
+
+/// Menu input taken away while capturing, so the captured press
+/// doesn't also navigate or unpause.
+#[derive(Component)]
+struct SuspendedInput;
+
+fn open_controls(
+    _: Trigger<OpenControls>,
+    existing: Query<(), With<ControlsPanel>>,
+    mut directional_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    mut commands: Commands,
+    server: Res<AssetServer>,
+) {
+    if !existing.is_empty() {
+        return;
+    }
+
+    let font = server.load("fonts/raster-forge.ttf");
+    let panel = commands
+        .spawn((
+            ControlsPanel,
+            widgets::overlay(&input_focus, 11, Color::BLACK, 4.0),
+            children![label("Controls", 200.0, &font)],
+        ))
+        .id();
+
+    let mut buttons = Vec::new();
+    for control in Control::ALL {
+        let rebind = commands
+            .spawn(button("Rebind", ControlsButton::Rebind(control), &font))
+            .id();
+        let context = commands
+            .spawn(label(control.context().name(), 150.0, &font))
+            .id();
+        let name = commands.spawn(label(control.name(), 200.0, &font)).id();
+        let inputs = commands
+            .spawn((BindingText(control), label("", 400.0, &font)))
+            .id();
+
+        commands
+            .spawn((row(), ChildOf(panel)))
+            .add_children(&[context, name, inputs, rebind]);
+        buttons.push(rebind);
+    }
+
+    commands.spawn((
+        ControlsStatus,
+        label("", 800.0, &font),
+        TextColor(Color::srgb(0.6, 0.6, 0.6)),
+        ChildOf(panel),
+    ));
+
+    let reset = commands
+        .spawn(button("Reset to defaults", ControlsButton::Reset, &font))
+        .id();
+    let back = commands
+        .spawn(button("Back", ControlsButton::Back, &font))
+        .id();
+    commands
+        .spawn((row(), ChildOf(panel)))
+        .add_children(&[reset, back]);
+
+    buttons.extend([reset, back]);
+    directional_nav_map.add_looping_edges(&buttons, CompassOctant::South);
+
+    input_focus.set(buttons[0]);
+}
+
+fn label(text: &str, width: f32, font: &Handle<Font>) -> impl Bundle {
+    (
+        Text::new(text),
+        TextFont {
+            font: font.clone(),
+            font_size: 24.0,
+            ..Default::default()
+        },
+        Node {
+            width: Val::Px(width),
+            ..default()
+        },
+    )
+}
+
+fn row() -> impl Bundle {
+    Node {
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(20.0),
+        ..default()
+    }
+}
+
+fn button(text: &'static str, action: ControlsButton, font: &Handle<Font>) -> impl Bundle {
+    (
+        action,
+        widgets::button(
+            text,
+            24.0,
+            Node {
+                padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                ..default()
+            },
+            font,
+        ),
+    )
+}
+
+fn click_button(
+    trigger: Trigger<Pointer<Click>>,
+    buttons: Query<&ControlsButton>,
+    panel: Single<(Entity, Has<Capture>), With<ControlsPanel>>,
+    menu_input: Query<Entity, With<Actions<InventoryContext>>>,
+    mut status: Single<&mut Text, With<ControlsStatus>>,
+    mut controls: ResMut<Controls>,
+    mut commands: Commands,
+) -> Result {
+    let Ok(button) = buttons.get(trigger.target()) else {
+        return Ok(());
+    };
+
+    let (panel, capturing) = panel.into_inner();
+    if capturing {
+        return Ok(());
+    }
+
+    match button {
+        ControlsButton::Rebind(control) => {
+            status.0 = format!("Press a key or button for {}...", control.name());
+            commands.entity(panel).insert(Capture {
+                control: *control,
+                phase: CapturePhase::Arming,
+                timer: Timer::from_seconds(CAPTURE_SECS, TimerMode::Once),
+            });
+
+            for entity in menu_input.iter() {
+                commands
+                    .entity(entity)
+                    .remove::<Actions<InventoryContext>>()
+                    .insert(SuspendedInput);
+            }
+        }
+        ControlsButton::Reset => {
+            controls.reset();
+            controls.save()?;
+            status.0 = "Controls reset to defaults.".into();
+        }
+        ControlsButton::Back => {
+            commands.run_system_cached(close_controls);
+        }
+    }
+
+    Ok(())
+}
+
+fn capture_input(
+    panel: Option<Single<(Entity, &mut Capture)>>,
+    suspended: Query<Entity, With<SuspendedInput>>,
+    mut status: Single<&mut Text, With<ControlsStatus>>,
+    mut controls: ResMut<Controls>,
+    keys: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    time: Res<Time>,
+    mut commands: Commands,
+) -> Result {
+    let Some(panel) = panel else {
+        return Ok(());
+    };
+    let (panel, mut capture) = panel.into_inner();
+
+    let anything_pressed = keys.get_pressed().next().is_some()
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.get_pressed().next().is_some());
+
+    match capture.phase {
+        CapturePhase::Arming => {
+            if !anything_pressed {
+                capture.phase = CapturePhase::Listening;
+            }
+        }
+        CapturePhase::Listening => {
+            let input = keys
+                .get_just_pressed()
+                .next()
+                .map(|key| ControlInput::Key(*key))
+                .or_else(|| {
+                    gamepads
+                        .iter()
+                        .find_map(|gamepad| gamepad.get_just_pressed().next())
+                        .map(|button| ControlInput::Gamepad(*button))
+                });
+
+            if let Some(input) = input {
+                status.0 = match controls.assign(capture.control, input) {
+                    Ok(()) => {
+                        controls.save()?;
+                        format!(
+                            "{} is now bound to {}.",
+                            capture.control.name(),
+                            input.label()
+                        )
+                    }
+                    Err(other) => format!("{} is already used by {}.", input.label(), other.name()),
+                };
+                capture.phase = CapturePhase::Releasing;
+            } else if capture.timer.tick(time.delta()).finished() {
+                status.0 = "Rebinding cancelled.".into();
+                capture.phase = CapturePhase::Releasing;
+            }
+        }
+        CapturePhase::Releasing => {
+            if !anything_pressed {
+                commands.entity(panel).remove::<Capture>();
+                for entity in suspended.iter() {
+                    commands
+                        .entity(entity)
+                        .remove::<SuspendedInput>()
+                        .insert(Actions::<InventoryContext>::default());
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn show_bindings(
+    controls: Res<Controls>,
+    added: Query<(), Added<BindingText>>,
+    mut texts: Query<(&BindingText, &mut Text)>,
+) {
+    if !controls.is_changed() && added.is_empty() {
+        return;
+    }
+
+    for (binding, mut text) in texts.iter_mut() {
+        text.0 = controls
+            .get(binding.0)
+            .iter()
+            .map(ControlInput::label)
+            .collect::<Vec<_>>()
+            .join(", ");
+    }
+}
+
+fn close_controls(
+    panel: Option<Single<Entity, With<ControlsPanel>>>,
+    suspended: Query<Entity, With<SuspendedInput>>,
+    mut commands: Commands,
+) {
+    let Some(panel) = panel else {
+        return;
+    };
+
+    commands.run_system_cached_with(widgets::close_overlay, *panel);
+
+    for entity in suspended.iter() {
+        commands
+            .entity(entity)
+            .remove::<SuspendedInput>()
+            .insert(Actions::<InventoryContext>::default());
+    }
+}
@@ -0,0 +1,300 @@
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::{Actions, Fired, InputAction};
+
+use crate::GameState;
+use crate::inventory::input::{InteractAction, InventoryContext, UnpauseAction};
+
+pub struct CreditsPlugin;
+
+impl Plugin for CreditsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Credits), spawn_roll)
+            .add_systems(Update, scroll_roll.run_if(in_state(GameState::Credits)))
+            .add_systems(
+                OnExit(GameState::Credits),
+                crate::despawn_entities::<With<Credits>>,
+            )
+            .add_observer(skip_credits::<InteractAction>)
+            .add_observer(skip_credits::<UnpauseAction>);
+    }
+}
+
+const CREDITS: &str = include_str!("../credits/CREDITS.md");
+
+// `LICENSES`: every file in `credits/licenses`, named after the project it covers.
+include!(concat!(env!("OUT_DIR"), "/licenses.rs"));
+
+/// How fast the roll moves up the screen, in pixels per second.
+const SCROLL_SPEED: f32 = 60.0;
+
+/// The credits, as parsed from the markdown in `credits/`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CreditsDocument {
+    pub title: String,
+    pub sections: Vec<CreditsSection>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CreditsSection {
+    pub heading: String,
+    pub entries: Vec<String>,
+}
+
+impl CreditsDocument {
+    /// Parse a markdown document into sections.
+    ///
+    /// The `#` heading is the title, each `##` heading starts a section,
+    /// and every list item or line of text beneath it is an entry.
+    pub fn parse(markdown: &str) -> Self {
+        let mut document = Self::default();
+
+        for line in markdown.lines().map(str::trim) {
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(heading) = line.strip_prefix("## ") {
+                document.sections.push(CreditsSection {
+                    heading: heading.trim().into(),
+                    entries: Vec::new(),
+                });
+            } else if let Some(title) = line.strip_prefix("# ") {
+                document.title = title.trim().into();
+            } else {
+                let entry = line
+                    .strip_prefix("- ")
+                    .or_else(|| line.strip_prefix("* "))
+                    .unwrap_or(line)
+                    .trim();
+
+                match document.sections.last_mut() {
+                    Some(section) => section.entries.push(display_entry(entry)),
+                    None => document.sections.push(CreditsSection {
+                        heading: String::new(),
+                        entries: vec![display_entry(entry)],
+                    }),
+                }
+            }
+        }
+
+        document
+    }
+
+    /// The shipped credits, followed by a section listing the licenses.
+    pub fn load() -> Self {
+        let mut document = Self::parse(CREDITS);
+
+        document.sections.push(CreditsSection {
+            heading: "Licenses".into(),
+            entries: LICENSES
+                .iter()
+                .map(|(project, license)| {
+                    let name = license
+                        .lines()
+                        .map(str::trim)
+                        .find(|line| !line.is_empty())
+                        .unwrap_or_default();
+                    format!("{project}: {name}")
+                })
+                .collect(),
+        });
+
+        document
+    }
+}
+
+/// Links read better without their scheme.
+fn display_entry(entry: &str) -> String {
+    entry
+        .strip_prefix("https://")
+        .or_else(|| entry.strip_prefix("http://"))
+        .unwrap_or(entry)
+        .into()
+}
+
+/// Everything spawned for the credits, removed when they end.
+#[derive(Component)]
+struct Credits;
+
+/// The column of text moving up the screen, `offset` pixels from the top.
+#[derive(Component)]
+struct CreditsRoll {
+    offset: f32,
+}
+
+fn spawn_roll(mut commands: Commands, server: Res<AssetServer>) {
+    commands.spawn((Credits, Camera2d, Msaa::Off));
+
+    let font = server.load("fonts/raster-forge.ttf");
+    let text = |text: String, size: f32, color: Color| {
+        (
+            Text::new(text),
+            TextFont {
+                font: font.clone(),
+                font_size: size,
+                ..Default::default()
+            },
+            TextColor(color),
+            TextLayout::new_with_justify(JustifyText::Center),
+        )
+    };
+
+    let document = CreditsDocument::load();
+    let start = crate::HEIGHT * crate::RESOLUTION_SCALE;
+
+    let screen = commands
+        .spawn((
+            Credits,
+            Actions::<InventoryContext>::default(),
+            BackgroundColor(Color::BLACK),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+        ))
+        .id();
+
+    let roll = commands
+        .spawn((
+            CreditsRoll { offset: start },
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(start),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(12.0),
+                ..default()
+            },
+            ChildOf(screen),
+        ))
+        .id();
+
+    commands.spawn((
+        text(document.title, 64.0, Color::WHITE),
+        Node {
+            margin: UiRect::bottom(Val::Px(40.0)),
+            ..default()
+        },
+        ChildOf(roll),
+    ));
+
+    for section in document.sections {
+        if !section.heading.is_empty() {
+            commands.spawn((
+                text(section.heading, 42.0, Color::WHITE),
+                Node {
+                    margin: UiRect::top(Val::Px(40.0)),
+                    ..default()
+                },
+                ChildOf(roll),
+            ));
+        }
+
+        for entry in section.entries {
+            commands.spawn((text(entry, 28.0, Color::srgb(0.6, 0.6, 0.6)), ChildOf(roll)));
+        }
+    }
+
+    commands.spawn((
+        text("Thank you for playing.".into(), 42.0, Color::WHITE),
+        Node {
+            margin: UiRect::top(Val::Px(120.0)),
+            ..default()
+        },
+        ChildOf(roll),
+    ));
+}
+
+fn scroll_roll(
+    roll: Single<(&mut CreditsRoll, &mut Node, &ComputedNode)>,
+    time: Res<Time>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let (mut roll, mut node, computed) = roll.into_inner();
+
+    roll.offset -= SCROLL_SPEED * time.delta_secs();
+    node.top = Val::Px(roll.offset);
+
+    let height = computed.size().y * computed.inverse_scale_factor();
+    if height > 0.0 && roll.offset < -height {
+        next_state.set(GameState::Menu);
+    }
+}
+
+fn skip_credits<A: InputAction>(
+    trigger: Trigger<Fired<A>>,
+    credits: Query<(), With<Credits>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if credits.get(trigger.target()).is_ok() {
+        info!("skipping credits");
+        next_state.set(GameState::Menu);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_title_and_sections() {
+        let document = CreditsDocument::parse(
+            "# Credits\n\n## Art\n\n- Tiles\n* Furniture\n\n## Music\nSomeone\n",
+        );
+
+        assert_eq!(document.title, "Credits");
+        assert_eq!(
+            document.sections,
+            [
+                CreditsSection {
+                    heading: "Art".into(),
+                    entries: vec!["Tiles".into(), "Furniture".into()],
+                },
+                CreditsSection {
+                    heading: "Music".into(),
+                    entries: vec!["Someone".into()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn entries_before_a_heading_get_their_own_section() {
+        let document = CreditsDocument::parse("Made with love\n\n## Art\n- Tiles");
+
+        assert_eq!(document.title, "");
+        assert_eq!(document.sections[0].heading, "");
+        assert_eq!(document.sections[0].entries, ["Made with love"]);
+        assert_eq!(document.sections[1].entries, ["Tiles"]);
+    }
+
+    #[test]
+    fn links_drop_their_scheme() {
+        let document = CreditsDocument::parse(
+            "## Assets\n- https://example.itch.io/tiles\n- http://example.com/font",
+        );
+
+        assert_eq!(
+            document.sections[0].entries,
+            ["example.itch.io/tiles", "example.com/font"]
+        );
+    }
+
+    #[test]
+    fn lists_every_license() {
+        let document = CreditsDocument::load();
+        let licenses = document.sections.last().unwrap();
+
+        assert_eq!(licenses.heading, "Licenses");
+        assert_eq!(licenses.entries.len(), LICENSES.len());
+        assert!(licenses.entries.contains(&"Bevy: MIT License".to_string()));
+        assert!(
+            licenses
+                .entries
+                .contains(&"Bevy game template: Creative Commons Legal Code".to_string())
+        );
+    }
+}
@@ -1,12 +1,23 @@
+use super::NextAutoAdvance;
 use super::fragments::IntoBox;
 use bevy::prelude::*;
 use bevy_seedling::prelude::*;
 use bevy_sequence::prelude::FragmentExt;
+use serde::Deserialize;
 use std::sync::Arc;
 
-use crate::textbox::{CharacterEvent, CharacterSprite, glyph_sample};
+use crate::audio::GlyphPool;
+use crate::textbox::{
+    AutoAdvance, CharacterEvent, CharacterSprite, ExpressionEvent, Speaker, glyph_sample,
+};
 
-#[derive(Clone, Copy)]
+/// The portrait a character is shown with unless a line picks another.
+pub const NEUTRAL: &str = "neutral";
+
+/// The shadow letting its face show.
+pub const DISTORTED: &str = "distorted";
+
+#[derive(Debug, Clone, Copy, Deserialize)]
 pub enum Chara {
     Narrator,
     DistressedNarrator1,
@@ -19,24 +30,87 @@ pub enum Chara {
 }
 
 impl Chara {
-    pub fn sprite(&self) -> Option<CharacterSprite> {
+    pub const ALL: [Self; 8] = [
+        Self::Narrator,
+        Self::DistressedNarrator1,
+        Self::DistressedNarrator2,
+        Self::Father,
+        Self::Luna,
+        Self::Stranger,
+        Self::Sturgeon,
+        Self::Shadow,
+    ];
+
+    /// The name shown next to this character's lines in the backlog.
+    pub fn name(&self) -> Option<&'static str> {
         match self {
             Self::Narrator => None,
             Self::DistressedNarrator1 => None,
             Self::DistressedNarrator2 => None,
-            Self::Father => Some(CharacterSprite::new("main.png")),
-            Self::Luna => Some(CharacterSprite::new("luna.png")),
-            Self::Stranger => None,
-            Self::Sturgeon => Some(CharacterSprite::new("sturgeon.png")),
-            Self::Shadow => Some(CharacterSprite::new("shadow.png")),
+            Self::Father => Some("Father"),
+            Self::Luna => Some("Luna"),
+            Self::Stranger => Some("Stranger"),
+            Self::Sturgeon => Some("Sturgeon"),
+            Self::Shadow => Some("Shadow"),
         }
     }
 
+    /// Each expression this character has a portrait for, by name.
+    ///
+    /// Animated portraits are a row of full screen frames. Characters
+    /// without art, like the narrator and the stranger, have none. Nobody
+    /// has smiling or worried art yet, so lines asking for those fall back
+    /// to neutral.
+    pub fn portraits(&self) -> Vec<(&'static str, CharacterSprite)> {
+        match self {
+            Self::Narrator => Vec::new(),
+            Self::DistressedNarrator1 => Vec::new(),
+            Self::DistressedNarrator2 => Vec::new(),
+            Self::Father => vec![(NEUTRAL, CharacterSprite::new("main.png"))],
+            Self::Luna => vec![(NEUTRAL, CharacterSprite::new("luna.png"))],
+            Self::Stranger => Vec::new(),
+            Self::Sturgeon => vec![(
+                NEUTRAL,
+                CharacterSprite::animated("textures/characters/sturgeon-flicker.png", 0.08, 0..4),
+            )],
+            Self::Shadow => vec![
+                (
+                    NEUTRAL,
+                    CharacterSprite::animated("textures/mega-swiggle.png", 0.1, 0..5),
+                ),
+                (
+                    DISTORTED,
+                    CharacterSprite::Still("textures/face.png".into()),
+                ),
+            ],
+        }
+    }
+
+    /// This character's portrait for `expression`, or their neutral one if
+    /// they don't have it.
+    pub fn portrait(&self, expression: &str) -> Option<CharacterSprite> {
+        let mut portraits = self.portraits();
+        let index = portraits
+            .iter()
+            .position(|(name, _)| *name == expression)
+            .or_else(|| {
+                warn!("{self:?} has no `{expression}` portrait");
+                portraits.iter().position(|(name, _)| *name == NEUTRAL)
+            })?;
+
+        Some(portraits.swap_remove(index).1)
+    }
+
+    pub fn sprite(&self) -> Option<CharacterSprite> {
+        self.portrait(NEUTRAL)
+    }
+
     pub fn glyphs(&self) -> Arc<dyn Fn(&mut Commands, &AssetServer) + Send + Sync> {
         match self {
             Self::Narrator => Arc::new(move |commands, server| {
                 commands.spawn((
                     PitchRange::new(0.02),
+                    GlyphPool,
                     SamplePlayer {
                         sample: server.load(glyph_sample("low.wav")),
                         volume: Volume::Linear(0.5),
@@ -47,6 +121,7 @@ impl Chara {
             Self::DistressedNarrator1 => Arc::new(move |commands, server| {
                 commands.spawn((
                     PitchRange(1.0..1.15),
+                    GlyphPool,
                     SamplePlayer {
                         sample: server.load(glyph_sample("low.wav")),
                         volume: Volume::Linear(0.6),
@@ -57,6 +132,7 @@ impl Chara {
             Self::DistressedNarrator2 => Arc::new(move |commands, server| {
                 commands.spawn((
                     PitchRange(1.0..1.30),
+                    GlyphPool,
                     SamplePlayer {
                         sample: server.load(glyph_sample("low.wav")),
                         volume: Volume::Linear(0.7),
@@ -67,6 +143,7 @@ impl Chara {
             Self::Father => Arc::new(move |commands, server| {
                 commands.spawn((
                     PitchRange::new(0.02),
+                    GlyphPool,
                     SamplePlayer {
                         sample: server.load(glyph_sample("medium.wav")),
                         volume: Volume::Linear(0.5),
@@ -77,6 +154,7 @@ impl Chara {
             Self::Luna => Arc::new(move |commands, server| {
                 commands.spawn((
                     PitchRange(0.75..0.85),
+                    GlyphPool,
                     SamplePlayer {
                         sample: server.load(glyph_sample("high.wav")),
                         volume: Volume::Linear(0.5),
@@ -87,6 +165,7 @@ impl Chara {
             Self::Stranger => Arc::new(move |commands, server| {
                 commands.spawn((
                     PitchRange(0.75..0.85),
+                    GlyphPool,
                     SamplePlayer {
                         sample: server.load(glyph_sample("low.wav")),
                         volume: Volume::Linear(0.5),
@@ -97,6 +176,7 @@ impl Chara {
             Self::Sturgeon | Self::Shadow => Arc::new(move |commands, server| {
                 commands.spawn((
                     PitchRange(0.45..0.75),
+                    GlyphPool,
                     SamplePlayer {
                         sample: server.load(glyph_sample("low.wav")),
                         volume: Volume::Linear(0.5),
@@ -117,7 +197,34 @@ where
             let sprite = chara.sprite();
             let glyph = chara.glyphs();
 
-            writer.write(CharacterEvent { sprite, glyph });
+            writer.write(CharacterEvent {
+                speaker: Speaker::Chara(chara),
+                sprite,
+                glyph,
+            });
+        })
+    }
+
+    /// Show the speaker's portrait for `expression` with this line.
+    fn expr(self, expression: impl Into<String>) -> impl IntoBox<C> {
+        let expression = expression.into();
+        self.on_start(move |mut writer: EventWriter<ExpressionEvent>| {
+            writer.write(ExpressionEvent(expression.clone()));
+        })
+    }
+
+    /// Move on from this line by itself after `secs`, ignoring input.
+    ///
+    /// ```ignore
+    /// "lock the door".distressed_narrator2().auto(1.5)
+    /// ```
+    fn auto(self, secs: f32) -> impl IntoBox<C> {
+        self.auto_with(AutoAdvance::new(secs))
+    }
+
+    fn auto_with(self, auto: AutoAdvance) -> impl IntoBox<C> {
+        self.on_start(move |mut next: ResMut<NextAutoAdvance>| {
+            next.0 = Some(auto);
         })
     }
 
@@ -1,10 +1,13 @@
 use bevy::prelude::*;
 use bevy_sequence::{fragment::DataLeaf, prelude::*};
-use std::{marker::PhantomData, time::Duration};
+use std::{marker::PhantomData, sync::Arc, time::Duration};
 
+use super::script::Script;
+use super::skip::CutscenePlayback;
 use crate::{
     player::{InhibitAddEvent, InhibitRemoveEvent},
-    textbox::TextboxCloseEvent,
+    sequence::rooted,
+    textbox::{AutoAdvance, TextboxCloseEvent},
 };
 
 pub trait IntoBox<C = EmptyCutscene>: IntoFragment<CutsceneFragment, TextBoxContext<C>> {
@@ -18,21 +21,28 @@ where
 {
     fn spawn_box(self, commands: &mut Commands) {
         spawn_root_with(
-            self.on_start(
-                |player: Single<Entity, With<crate::player::Player>>, mut commands: Commands| {
-                    commands.entity(*player).trigger(InhibitAddEvent);
-                },
-            )
-            .on_end(
-                |mut writer: EventWriter<TextboxCloseEvent>,
-                 player: Option<Single<Entity, With<crate::player::Player>>>,
-                 mut commands: Commands| {
-                    writer.write(TextboxCloseEvent);
-
-                    if let Some(player) = player {
-                        commands.entity(*player).trigger(InhibitRemoveEvent);
-                    }
-                },
+            rooted(
+                self.on_start(
+                    |player: Single<Entity, With<crate::player::Player>>,
+                     mut playback: ResMut<CutscenePlayback>,
+                     mut commands: Commands| {
+                        playback.begin();
+                        commands.entity(*player).trigger(InhibitAddEvent);
+                    },
+                )
+                .on_end(
+                    |mut writer: EventWriter<TextboxCloseEvent>,
+                     player: Option<Single<Entity, With<crate::player::Player>>>,
+                     mut playback: ResMut<CutscenePlayback>,
+                     mut commands: Commands| {
+                        playback.end();
+                        writer.write(TextboxCloseEvent);
+
+                        if let Some(player) = player {
+                            commands.entity(*player).trigger(InhibitRemoveEvent);
+                        }
+                    },
+                ),
             ),
             commands,
             TextBoxContext::new(),
@@ -60,8 +70,42 @@ impl<C> TextBoxContext<C> {
 
 #[derive(Debug, Clone)]
 pub enum CutsceneFragment {
-    Dialog(String),
+    /// A line of dialogue, which moves on by itself if it has an `auto`.
+    Dialog {
+        text: String,
+        auto: Option<AutoAdvance>,
+    },
     Pause(Duration),
+    /// Play a [`DialogueScript`](super::script::DialogueScript) asset.
+    Script(&'static str),
+    /// Show a line with a list of options for the player to pick from.
+    Choice {
+        prompt: String,
+        options: Vec<String>,
+    },
+    /// Continue with the arm matching the most recent choice.
+    Branch(Vec<BranchArm>),
+}
+
+/// Plays one arm of a [`CutsceneFragment::Branch`], ending the given
+/// event when it's done.
+#[derive(Clone)]
+pub struct BranchArm(Arc<dyn Fn(&mut Commands, FragmentEndEvent) + Send + Sync>);
+
+impl BranchArm {
+    pub fn new(arm: impl Fn(&mut Commands, FragmentEndEvent) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(arm))
+    }
+
+    pub fn play(&self, commands: &mut Commands, end: FragmentEndEvent) {
+        (self.0)(commands, end)
+    }
+}
+
+impl std::fmt::Debug for BranchArm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BranchArm").finish_non_exhaustive()
+    }
 }
 
 impl From<f32> for CutsceneFragment {
@@ -78,13 +122,19 @@ impl From<Duration> for CutsceneFragment {
 
 impl From<String> for CutsceneFragment {
     fn from(value: String) -> Self {
-        Self::Dialog(value)
+        Self::Dialog {
+            text: value,
+            auto: None,
+        }
     }
 }
 
 impl<'a> From<&'a str> for CutsceneFragment {
     fn from(value: &'a str) -> Self {
-        Self::Dialog(value.into())
+        Self::Dialog {
+            text: value.into(),
+            auto: None,
+        }
     }
 }
 
@@ -107,7 +157,9 @@ macro_rules! impl_into_frag {
     };
 }
 
+impl_into_frag!(CutsceneFragment, slf, slf);
 impl_into_frag!(&'static str, slf, slf);
 impl_into_frag!(String, slf, slf);
 impl_into_frag!(Duration, slf, slf);
 impl_into_frag!(f32, slf, slf);
+impl_into_frag!(Script, slf, CutsceneFragment::Script(slf.0));
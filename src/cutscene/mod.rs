@@ -6,11 +6,17 @@ use bevy_sequence::{
 use fragments::IntoBox;
 use std::{any::TypeId, collections::VecDeque};
 
-use crate::textbox::{TextBlurb, TextboxCloseEvent, TextboxCloseInteraction, TextboxEvent};
+use crate::reset::ResetAppExt;
+use crate::textbox::{
+    AutoAdvance, ChoiceSelected, TextBlurb, TextboxCloseEvent, TextboxCloseInteraction,
+    TextboxEvent,
+};
 
 pub mod chara;
 pub mod fragments;
 pub mod movement;
+pub mod script;
+pub mod skip;
 
 pub struct CutscenePlugin;
 
@@ -19,7 +25,18 @@ impl Plugin for CutscenePlugin {
         let mut cache = movement::MovementSystemCache::default();
         cache.0.insert(TypeId::of::<EasingCurve<Vec3>>());
 
-        app.init_resource::<FragmentEndEvents>()
+        app.add_plugins(skip::SkipPlugin)
+            .init_resource::<FragmentEndEvents>()
+            .init_resource::<LastChoice>()
+            .init_resource::<NextAutoAdvance>()
+            .reset_resource::<FragmentEndEvents>()
+            .reset_resource::<LastChoice>()
+            .reset_resource::<NextAutoAdvance>()
+            .despawn_on_reset::<Delay>()
+            .despawn_on_reset::<script::PendingScript>()
+            .init_asset::<script::DialogueScript>()
+            .init_asset_loader::<script::DialogueScriptLoader>()
+            .init_resource::<script::DialogueHooks>()
             .insert_resource(cache)
             .add_event::<FragmentEvent<fragments::CutsceneFragment>>()
             .add_systems(
@@ -29,11 +46,14 @@ impl Plugin for CutscenePlugin {
             )
             .add_systems(
                 PreUpdate,
-                fragment_bridge_start.after(bevy_sequence::SequenceSets::Emit),
+                (fragment_bridge_start, script::start_scripts)
+                    .chain()
+                    .after(bevy_sequence::SequenceSets::Emit),
             )
             .add_systems(
                 PostUpdate,
-                (fragment_bridge_end, tick_delay).before(bevy_sequence::SequenceSets::Respond),
+                (record_choice, fragment_bridge_end, tick_delay)
+                    .before(bevy_sequence::SequenceSets::Respond),
             );
     }
 }
@@ -51,6 +71,15 @@ impl IntoCurve<EasingCurve<Vec3>> for EaseFunction {
 #[derive(Resource, Default)]
 struct FragmentEndEvents(VecDeque<FragmentEndEvent>);
 
+/// The option picked in the most recent cutscene choice.
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct LastChoice(pub Option<usize>);
+
+/// How the next dialog line moves on by itself, set by
+/// [`Character::auto`](chara::Character::auto).
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct NextAutoAdvance(pub Option<AutoAdvance>);
+
 #[derive(Component)]
 struct Delay {
     timer: Timer,
@@ -64,15 +93,30 @@ fn fragment_bridge_start(
     mut close: EventWriter<TextboxCloseEvent>,
 
     mut ids: ResMut<FragmentEndEvents>,
+    mut fragment_end: EventWriter<FragmentEndEvent>,
     mut commands: Commands,
+    server: Res<AssetServer>,
+    last_choice: Res<LastChoice>,
+    mut next_auto: ResMut<NextAutoAdvance>,
+    mut playback: ResMut<skip::CutscenePlayback>,
 ) {
     for event in fragment_events.read() {
         match &event.data {
-            fragments::CutsceneFragment::Dialog(d) => {
+            fragments::CutsceneFragment::Dialog { .. } | fragments::CutsceneFragment::Pause(_)
+                if playback.skipping() =>
+            {
+                next_auto.0 = None;
+                fragment_end.write(event.end());
+            }
+            fragments::CutsceneFragment::Dialog { text, auto } => {
                 ids.0.push_back(event.end());
-                textbox.write(TextboxEvent::section_retained(TextBlurb::main_character(
-                    d.clone(),
-                )));
+
+                let mut line =
+                    TextboxEvent::section_retained(TextBlurb::main_character(text.clone()));
+                if let Some(auto) = auto.or(next_auto.0.take()) {
+                    line = line.with_auto_advance(auto);
+                }
+                textbox.write(line);
             }
             fragments::CutsceneFragment::Pause(p) => {
                 commands.spawn(Delay {
@@ -81,16 +125,48 @@ fn fragment_bridge_start(
                 });
                 close.write_default();
             }
+            fragments::CutsceneFragment::Script(path) => {
+                commands.spawn(script::PendingScript::new(server.load(*path), event.end()));
+            }
+            fragments::CutsceneFragment::Choice { prompt, options } => {
+                playback.stop_skipping();
+                ids.0.push_back(event.end());
+                textbox.write(TextboxEvent::section_retained(
+                    TextBlurb::main_character(prompt.clone()).with_choices(options.clone()),
+                ));
+            }
+            fragments::CutsceneFragment::Branch(arms) => {
+                match last_choice.0.and_then(|choice| arms.get(choice)) {
+                    Some(arm) => arm.play(&mut commands, event.end()),
+                    None => {
+                        fragment_end.write(event.end());
+                    }
+                }
+            }
         }
     }
 }
 
+fn record_choice(mut reader: EventReader<ChoiceSelected>, mut last: ResMut<LastChoice>) {
+    if let Some(choice) = reader.read().last() {
+        last.0 = Some(choice.0);
+    }
+}
+
 fn fragment_bridge_end(
     mut text_end: EventReader<TextboxCloseInteraction>,
     mut fragment_end: EventWriter<FragmentEndEvent>,
+    playback: Res<skip::CutscenePlayback>,
 
     mut ids: ResMut<FragmentEndEvents>,
 ) {
+    // Skipping only starts while no choice is waiting, so anything left is a dialog line.
+    if playback.skipping() {
+        text_end.clear();
+        fragment_end.write_batch(ids.0.drain(..));
+        return;
+    }
+
     for _event in text_end.read() {
         if let Some(end) = ids.0.pop_front() {
             fragment_end.write(end);
@@ -102,13 +178,14 @@ fn tick_delay(
     mut delays: Query<(Entity, &mut Delay)>,
     mut commands: Commands,
     time: Res<Time>,
+    playback: Res<skip::CutscenePlayback>,
 
     mut fragment_end: EventWriter<FragmentEndEvent>,
 ) {
-    let delta = time.delta();
+    let delta = playback.scale(time.delta());
 
     for (entity, mut delay) in delays.iter_mut() {
-        if delay.timer.tick(delta).just_finished() {
+        if playback.skipping() || delay.timer.tick(delta).just_finished() {
             commands.entity(entity).despawn();
             fragment_end.write(delay.id);
         }
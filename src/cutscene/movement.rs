@@ -6,7 +6,7 @@ use std::any::TypeId;
 use std::marker::PhantomData;
 use std::time::Duration;
 
-use super::{IntoCurve, fragments::IntoBox};
+use super::{IntoCurve, fragments::IntoBox, skip::CutscenePlayback};
 
 /// This is applied to entities whose movement should be handled
 /// purely by cutscene directives.
@@ -36,6 +36,12 @@ where
         self.timer.tick(duration);
     }
 
+    /// Jump to the end of the clip.
+    pub fn finish(&mut self) {
+        let remaining = self.timer.remaining();
+        self.timer.tick(remaining);
+    }
+
     pub fn complete(&self) -> bool {
         self.timer.finished()
     }
@@ -192,10 +198,15 @@ pub fn apply_movements<C: Curve<Vec3> + Threaded>(
         With<CutsceneMovement>,
     >,
     time: Res<Time>,
+    playback: Res<CutscenePlayback>,
     mut commands: Commands,
 ) {
     for (entity, mut transform, mut clip, mut velocity) in q.iter_mut() {
-        clip.tick(time.delta());
+        if playback.skipping() {
+            clip.finish();
+        } else {
+            clip.tick(playback.scale(time.delta()));
+        }
 
         if let Some(new_position) = clip.position() {
             let difference = new_position - transform.translation;
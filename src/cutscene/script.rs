@@ -0,0 +1,261 @@
+use std::sync::Arc;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::ecs::system::SystemId;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy_sequence::prelude::*;
+use serde::Deserialize;
+
+use super::chara::{Chara, Character, NEUTRAL};
+use super::fragments::{BranchArm, CutsceneFragment, IntoBox, TextBoxContext};
+use crate::sequence::rooted;
+use crate::textbox::AutoAdvance;
+
+/// A dialogue script loaded from a `.dialogue.ron` file.
+///
+/// ```ron
+/// (
+///     lines: [
+///         Pause(0.5),
+///         Line(Father, "Hello?"),
+///         Say(speaker: Shadow, text: "Heh heh.", expr: Some("distorted")),
+///         Hook("knock"),
+///         Choice(Father, "Open the door?", [
+///             ("Yes", [Hook("open")]),
+///             ("No", [Line(Father, "Not tonight.")]),
+///         ]),
+///     ],
+/// )
+/// ```
+///
+/// Scripts are played with [`script`], which produces a regular cutscene
+/// fragment that can be combined with any other `IntoBox` fragment. Once
+/// it starts, each step is played as its own [`CutsceneFragment`], so
+/// scripted lines are shown, skipped and backlogged like any other.
+#[derive(Debug, Asset, TypePath, Deserialize)]
+pub struct DialogueScript {
+    pub lines: Vec<ScriptStep>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub enum ScriptStep {
+    /// A line of dialogue spoken by a character.
+    Line(Chara, String),
+    /// A line of dialogue with a particular expression, or that moves on
+    /// by itself after `auto` seconds.
+    Say {
+        speaker: Chara,
+        text: String,
+        #[serde(default)]
+        expr: Option<String>,
+        #[serde(default)]
+        auto: Option<f32>,
+    },
+    /// Close the textbox and wait for the given number of seconds.
+    Pause(f32),
+    /// Run the hook registered under this name with
+    /// [`DialogueAppExt::register_dialogue_hook`].
+    Hook(String),
+    /// A line with options for the player to pick from, each followed by
+    /// the steps to play when it's picked.
+    Choice(Chara, String, Vec<(String, Vec<ScriptStep>)>),
+}
+
+#[derive(Default)]
+pub struct DialogueScriptLoader;
+
+impl AssetLoader for DialogueScriptLoader {
+    type Asset = DialogueScript;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["dialogue.ron"]
+    }
+}
+
+/// Named side effects that scripts can trigger with [`ScriptStep::Hook`].
+#[derive(Default, Resource)]
+pub struct DialogueHooks(HashMap<String, SystemId>);
+
+pub trait DialogueAppExt {
+    fn register_dialogue_hook<M>(
+        &mut self,
+        name: impl Into<String>,
+        system: impl IntoSystem<(), (), M> + 'static,
+    ) -> &mut Self;
+}
+
+impl DialogueAppExt for App {
+    fn register_dialogue_hook<M>(
+        &mut self,
+        name: impl Into<String>,
+        system: impl IntoSystem<(), (), M> + 'static,
+    ) -> &mut Self {
+        let id = self.world_mut().register_system(system);
+        self.world_mut()
+            .get_resource_or_init::<DialogueHooks>()
+            .0
+            .insert(name.into(), id);
+        self
+    }
+}
+
+/// Play the dialogue script at `path`, relative to the assets directory.
+pub fn script(path: &'static str) -> Script {
+    Script(path)
+}
+
+pub struct Script(pub &'static str);
+
+/// A script fragment waiting on its asset before it can be played.
+#[derive(Component)]
+pub(super) struct PendingScript {
+    script: Handle<DialogueScript>,
+    end: FragmentEndEvent,
+}
+
+impl PendingScript {
+    pub fn new(script: Handle<DialogueScript>, end: FragmentEndEvent) -> Self {
+        Self { script, end }
+    }
+}
+
+pub(super) fn start_scripts(
+    pending: Query<(Entity, &PendingScript)>,
+    scripts: Res<Assets<DialogueScript>>,
+    server: Res<AssetServer>,
+    mut fragment_end: EventWriter<FragmentEndEvent>,
+    mut commands: Commands,
+) {
+    for (entity, pending) in pending.iter() {
+        let Some(script) = scripts.get(&pending.script) else {
+            if server.load_state(&pending.script).is_failed() {
+                error!("failed to load dialogue script {:?}", pending.script.path());
+                commands.entity(entity).despawn();
+                fragment_end.write(pending.end);
+            }
+
+            continue;
+        };
+
+        commands.entity(entity).despawn();
+        play_steps(script.lines.clone().into(), 0, &mut commands, pending.end);
+    }
+}
+
+/// Play `steps` from `index` on, ending `end` once the last one finishes.
+fn play_steps(
+    steps: Arc<[ScriptStep]>,
+    index: usize,
+    commands: &mut Commands,
+    end: FragmentEndEvent,
+) {
+    let Some(step) = steps.get(index).cloned() else {
+        commands.send_event(end);
+        return;
+    };
+
+    let next = index + 1;
+    match step {
+        ScriptStep::Line(speaker, text) => {
+            let line = line(speaker, text, None, None);
+            play_then(line, commands, steps, next, end);
+        }
+        ScriptStep::Say {
+            speaker,
+            text,
+            expr,
+            auto,
+        } => {
+            let line = line(speaker, text, expr, auto);
+            play_then(line, commands, steps, next, end);
+        }
+        ScriptStep::Pause(secs) => {
+            play_then(CutsceneFragment::from(secs), commands, steps, next, end);
+        }
+        ScriptStep::Hook(name) => {
+            commands.queue(move |world: &mut World| run_hook(world, &name));
+            play_steps(steps, next, commands, end);
+        }
+        ScriptStep::Choice(speaker, prompt, options) => {
+            let choice = choice(speaker, prompt, options);
+            play_then(choice, commands, steps, next, end);
+        }
+    }
+}
+
+fn line(speaker: Chara, text: String, expr: Option<String>, auto: Option<f32>) -> impl IntoBox {
+    CutsceneFragment::Dialog {
+        text,
+        auto: auto.map(AutoAdvance::new),
+    }
+    .chara(speaker)
+    .expr(expr.unwrap_or_else(|| NEUTRAL.into()))
+}
+
+/// A choice followed by a branch into the steps for each option.
+fn choice(speaker: Chara, prompt: String, options: Vec<(String, Vec<ScriptStep>)>) -> impl IntoBox {
+    let (options, arms): (Vec<_>, Vec<_>) = options.into_iter().unzip();
+    let arms = arms
+        .into_iter()
+        .map(|arm| {
+            let arm: Arc<[ScriptStep]> = arm.into();
+            BranchArm::new(move |commands, end| play_steps(arm.clone(), 0, commands, end))
+        })
+        .collect();
+
+    (
+        CutsceneFragment::Choice { prompt, options }.chara(speaker),
+        CutsceneFragment::Branch(arms),
+    )
+}
+
+/// Play `fragment` on its own, then carry on from `steps[next]`.
+fn play_then(
+    fragment: impl IntoBox,
+    commands: &mut Commands,
+    steps: Arc<[ScriptStep]>,
+    next: usize,
+    end: FragmentEndEvent,
+) {
+    spawn_root_with(
+        rooted(
+            (fragment,)
+                .always()
+                .once()
+                .on_end(move |mut commands: Commands| {
+                    play_steps(steps.clone(), next, &mut commands, end);
+                }),
+        ),
+        commands,
+        TextBoxContext::new(),
+    );
+}
+
+fn run_hook(world: &mut World, name: &str) {
+    let Some(hook) = world
+        .get_resource::<DialogueHooks>()
+        .and_then(|hooks| hooks.0.get(name).copied())
+    else {
+        error!("no dialogue hook registered for `{name}`");
+        return;
+    };
+
+    if let Err(e) = world.run_system(hook) {
+        error!("failed to run dialogue hook `{name}`: {e}");
+    }
+}
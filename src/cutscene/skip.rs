@@ -0,0 +1,143 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_enhanced_input::prelude::*;
+
+use crate::controls::{Control, Controls};
+use crate::reset::ResetAppExt;
+use crate::textbox::{Textbox, TextboxCloseEvent, TextboxSections};
+
+/// How long the skip button has to be held before a cutscene is skipped.
+const SKIP_HOLD_SECS: f32 = 1.0;
+
+/// How much faster text and pauses run while fast-forwarding.
+const FAST_FORWARD_SPEED: f32 = 4.0;
+
+pub struct SkipPlugin;
+
+impl Plugin for SkipPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CutscenePlayback>()
+            .reset_resource::<CutscenePlayback>()
+            .add_input_context::<CutsceneControls>()
+            .add_systems(Startup, |mut commands: Commands| {
+                commands.spawn((
+                    Name::new("Cutscene controls"),
+                    Actions::<CutsceneControls>::default(),
+                ));
+            })
+            .add_observer(bind)
+            .add_observer(skip)
+            .add_observer(toggle_fast_forward);
+    }
+}
+
+/// How the running cutscenes are being played back.
+///
+/// Skipping ends every dialog line and pause as soon as it starts, so
+/// `on_start` and `on_end` side effects still run in order. It stops
+/// at the next choice, since the player has to pick an arm.
+#[derive(Debug, Default, Resource)]
+pub struct CutscenePlayback {
+    active: usize,
+    skipping: bool,
+    fast_forward: bool,
+}
+
+impl CutscenePlayback {
+    pub fn is_active(&self) -> bool {
+        self.active > 0
+    }
+
+    pub fn skipping(&self) -> bool {
+        self.skipping
+    }
+
+    /// The playback speed multiplier for text and timers.
+    pub fn speed(&self) -> f32 {
+        if self.is_active() && self.fast_forward {
+            FAST_FORWARD_SPEED
+        } else {
+            1.0
+        }
+    }
+
+    /// Scale a frame's `delta` by the playback speed.
+    pub fn scale(&self, delta: Duration) -> Duration {
+        delta.mul_f32(self.speed())
+    }
+
+    pub(super) fn begin(&mut self) {
+        self.active += 1;
+    }
+
+    pub(super) fn end(&mut self) {
+        self.active = self.active.saturating_sub(1);
+        if self.active == 0 {
+            self.skipping = false;
+        }
+    }
+
+    /// Stop skipping so the player can make a choice.
+    pub(super) fn stop_skipping(&mut self) {
+        self.skipping = false;
+    }
+}
+
+#[derive(InputContext)]
+pub struct CutsceneControls;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool)]
+struct SkipCutscene;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool, require_reset = true)]
+struct FastForward;
+
+fn bind(
+    trigger: Trigger<Binding<CutsceneControls>>,
+    mut actions: Query<&mut Actions<CutsceneControls>>,
+    controls: Res<Controls>,
+) -> Result {
+    let mut actions = actions.get_mut(trigger.target())?;
+
+    actions
+        .bind::<SkipCutscene>()
+        .to(controls.inputs(Control::SkipCutscene))
+        .with_conditions(Hold::new(SKIP_HOLD_SECS));
+
+    actions
+        .bind::<FastForward>()
+        .to(controls.inputs(Control::FastForward))
+        .with_conditions(JustPress::default());
+
+    Ok(())
+}
+
+fn skip(
+    _: Trigger<Fired<SkipCutscene>>,
+    mut playback: ResMut<CutscenePlayback>,
+    sections: Res<TextboxSections>,
+    textbox: Query<(), With<Textbox>>,
+    mut close: EventWriter<TextboxCloseEvent>,
+) {
+    if !playback.is_active() || playback.skipping || sections.awaiting_choice() {
+        return;
+    }
+
+    info!("skipping cutscene");
+    playback.skipping = true;
+
+    if !textbox.is_empty() {
+        close.write_default();
+    }
+}
+
+fn toggle_fast_forward(_: Trigger<Fired<FastForward>>, mut playback: ResMut<CutscenePlayback>) {
+    if !playback.is_active() {
+        return;
+    }
+
+    playback.fast_forward = !playback.fast_forward;
+}
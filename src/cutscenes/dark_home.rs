@@ -1,184 +1,146 @@
 use crate::{
+    GameState,
     animation::AnimationSprite,
     audio::MusicPool,
-    cutscene::{
-        chara::{Chara, Character},
-        fragments::IntoBox,
-    },
+    cutscene::{chara::Character, fragments::IntoBox, script::script},
     hook::Hook,
+    sequence::run_after,
 };
 use bevy::prelude::*;
 use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
 use bevy_seedling::prelude::*;
-use bevy_sequence::{combinators::delay::run_after, prelude::*};
+use bevy_sequence::prelude::*;
 use bevy_tween::prelude::*;
 use interpolate::sprite_color;
 
 pub fn sturgeon() -> impl IntoBox {
-    (
-        "how many times".chara(Chara::Sturgeon),
-        "will you tell her".chara(Chara::Sturgeon),
-        "how many".chara(Chara::Sturgeon),
-        "will you".chara(Chara::Sturgeon),
-    )
-        .always()
-        .once()
-}
-
-pub fn shadow_1() -> impl IntoBox {
-    // TODO: for some reason we need a tuple always???
-    ("you built a cage",).shadow().always().once()
+    (script("dialogue/sturgeon.dialogue.ron"),).always().once()
 }
 
-pub fn shadow_2() -> impl IntoBox {
-    ("do you need more painkillers yet?",)
-        .shadow()
-        .always()
-        .once()
-}
-
-pub fn shadow_3() -> impl IntoBox {
-    ("die already",).shadow().always().once()
-}
-
-pub fn shadow_4() -> impl IntoBox {
-    ("you'll be all alone",).shadow().always().once()
-}
-
-pub fn shadow_5() -> impl IntoBox {
-    ("it won't be long, now",).shadow().always().once()
-}
-
-pub fn shadow_6() -> impl IntoBox {
-    ("who is she, anyway",).shadow().always().once()
-}
-
-pub fn shadow_7() -> impl IntoBox {
-    ("alone",).shadow().always().once()
-}
-
-pub fn shadow_8() -> impl IntoBox {
-    ("oh god, the smell",).shadow().always().once()
-}
+/// The shadow's script for each scribble, in the order they're read.
+pub const SHADOW_SCRIPTS: [&str; 9] = [
+    "dialogue/shadow-1.dialogue.ron",
+    "dialogue/shadow-2.dialogue.ron",
+    "dialogue/shadow-3.dialogue.ron",
+    "dialogue/shadow-4.dialogue.ron",
+    "dialogue/shadow-5.dialogue.ron",
+    "dialogue/shadow-6.dialogue.ron",
+    "dialogue/shadow-7.dialogue.ron",
+    "dialogue/shadow-8.dialogue.ron",
+    "dialogue/shadow-9.dialogue.ron",
+];
 
-pub fn shadow_9() -> impl IntoBox {
-    ("you dropped your key",).shadow().always().once()
+/// What the shadow says at the `step`th scribble, counting from zero.
+pub fn shadow(step: usize) -> impl IntoBox {
+    (script(SHADOW_SCRIPTS[step]),).always().once()
 }
 
 pub fn final_cutscene() -> impl IntoBox {
     (
-        "Pill are scattered across the floor.".narrator().on_end(
-            |mut commands: Commands, server: Res<AssetServer>| {
-                commands.spawn((
-                    MusicPool,
-                    SamplePlayer::new(server.load("audio/music/luna.ogg"))
-                        .looping()
-                        .with_volume(Volume::Decibels(-8.0)),
-                    PlaybackSettings {
-                        speed: 0.5,
-                        ..Default::default()
-                    },
-                ));
-            },
-        ),
-        1.0,
-        "Luna?".father(),
-        "Honey, what's going on?".father(),
-        2.0,
-        "You gently shake her.".narrator(),
-        2.0,
-        "Luna, I'm scared.".father(),
-        2.0,
-        "She doesn't move.".narrator(),
-        "She's not waking up.".narrator(),
-        "She's not...".narrator(),
-        2.0,
-        "She's not breathing."
-            .distressed_narrator()
-            .on_start(lower_pitch),
-        4.0,
+        script("dialogue/dark-home.dialogue.ron"),
         "get out of here"
             .distressed_narrator()
+            .auto(1.5)
+            .on_start(lower_pitch),
+        "lock the door"
+            .distressed_narrator2()
+            .auto(1.5)
+            .on_start(lower_pitch),
+        "please..."
+            .distressed_narrator2()
+            .auto(2.0)
             .on_start(lower_pitch),
-        "lock the door".distressed_narrator2().on_start(lower_pitch),
-        "please...".distressed_narrator2().on_start(lower_pitch),
         2.0,
         "just... forget about this"
             .distressed_narrator2()
+            .auto(2.0)
             .on_start(lower_pitch),
         "like you forget everything else"
             .distressed_narrator2()
             .on_start(lower_pitch)
-            .on_start(|mut commands: Commands, server: Res<AssetServer>| {
-                let overlay = commands
-                    .spawn((
-                        crate::hook::Hook,
-                        HIGH_RES_LAYER,
-                        AnimationSprite::repeating("textures/mega-swiggle.png", 0.1, 0..5),
-                        Transform::from_xyz(0., 0., 900.)
-                            .with_scale(Vec3::splat(crate::RESOLUTION_SCALE)),
-                        children![
-                            SamplePlayer {
-                                sample: server.load("audio/sfx/whispers.wav"),
-                                volume: Volume::Linear(0.5),
-                                repeat_mode: RepeatMode::RepeatEndlessly,
-                            },
-                            SamplePlayer {
-                                sample: server.load("audio/sfx/hook.wav"),
-                                volume: Volume::Linear(0.5),
-                                ..Default::default()
-                            },
-                            SamplePlayer {
-                                sample: server.load("audio/sfx/wake-up.wav"),
-                                volume: Volume::Linear(0.5),
-                                ..Default::default()
-                            },
-                            SamplePlayer {
-                                sample: server.load("audio/sfx/many-whispers.wav"),
-                                volume: Volume::Linear(0.5),
-                                ..Default::default()
-                            },
-                        ],
-                    ))
-                    .id();
-
-                commands.entity(overlay).animation().insert_tween_here(
-                    Duration::from_secs(13),
-                    EaseKind::QuadraticOut,
-                    overlay
-                        .into_target()
-                        .with(sprite_color(Color::WHITE.with_alpha(0.0), Color::WHITE)),
-                );
-
-                let face = commands
-                    .spawn((
-                        Hook,
-                        HIGH_RES_LAYER,
-                        Transform::from_xyz(0., 0., 901.)
-                            .with_scale(Vec3::splat(crate::RESOLUTION_SCALE)),
-                        Sprite::from_image(server.load("textures/face.png")),
-                    ))
-                    .id();
-                commands.entity(face).animation().insert_tween_here(
-                    Duration::from_secs(13),
-                    EaseKind::QuadraticOut,
-                    face.into_target()
-                        .with(sprite_color(Color::WHITE.with_alpha(0.0), Color::WHITE)),
-                );
-
-                run_after(
-                    Duration::from_secs(10),
-                    |mut writer: EventWriter<AppExit>| {
-                        writer.write_default();
-                    },
-                    &mut commands,
-                );
-            }),
+            .on_start(the_hook),
     )
         .always()
         .once()
 }
 
-fn lower_pitch(mut music: Single<&mut PlaybackSettings, With<MusicPool>>) {
+/// Luna's theme, slowed down, as the scene in the bathroom sinks in.
+pub(super) fn slow_luna_theme(mut commands: Commands, server: Res<AssetServer>) {
+    commands.spawn((
+        MusicPool,
+        SamplePlayer::new(server.load("audio/music/luna.ogg"))
+            .looping()
+            .with_volume(Volume::Decibels(-8.0)),
+        PlaybackSettings {
+            speed: 0.5,
+            ..Default::default()
+        },
+    ));
+}
+
+/// Swallow the screen in scribbles and the face, then end the story.
+pub(super) fn the_hook(mut commands: Commands, server: Res<AssetServer>) {
+    let overlay = commands
+        .spawn((
+            Hook,
+            HIGH_RES_LAYER,
+            AnimationSprite::repeating("textures/mega-swiggle.png", 0.1, 0..5),
+            Transform::from_xyz(0., 0., 900.).with_scale(Vec3::splat(crate::RESOLUTION_SCALE)),
+            children![
+                SamplePlayer {
+                    sample: server.load("audio/sfx/whispers.wav"),
+                    volume: Volume::Linear(0.5),
+                    repeat_mode: RepeatMode::RepeatEndlessly,
+                },
+                SamplePlayer {
+                    sample: server.load("audio/sfx/hook.wav"),
+                    volume: Volume::Linear(0.5),
+                    ..Default::default()
+                },
+                SamplePlayer {
+                    sample: server.load("audio/sfx/wake-up.wav"),
+                    volume: Volume::Linear(0.5),
+                    ..Default::default()
+                },
+                SamplePlayer {
+                    sample: server.load("audio/sfx/many-whispers.wav"),
+                    volume: Volume::Linear(0.5),
+                    ..Default::default()
+                },
+            ],
+        ))
+        .id();
+
+    commands.entity(overlay).animation().insert_tween_here(
+        Duration::from_secs(13),
+        EaseKind::QuadraticOut,
+        overlay
+            .into_target()
+            .with(sprite_color(Color::WHITE.with_alpha(0.0), Color::WHITE)),
+    );
+
+    let face = commands
+        .spawn((
+            Hook,
+            HIGH_RES_LAYER,
+            Transform::from_xyz(0., 0., 901.).with_scale(Vec3::splat(crate::RESOLUTION_SCALE)),
+            Sprite::from_image(server.load("textures/face.png")),
+        ))
+        .id();
+    commands.entity(face).animation().insert_tween_here(
+        Duration::from_secs(13),
+        EaseKind::QuadraticOut,
+        face.into_target()
+            .with(sprite_color(Color::WHITE.with_alpha(0.0), Color::WHITE)),
+    );
+
+    run_after(
+        Duration::from_secs(10),
+        |mut commands: Commands| commands.set_state(GameState::Ending),
+        &mut commands,
+    );
+}
+
+pub(super) fn lower_pitch(mut music: Single<&mut PlaybackSettings, With<MusicPool>>) {
     music.speed *= 0.95;
 }
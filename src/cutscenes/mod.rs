@@ -1,27 +1,68 @@
 use bevy::prelude::*;
-use bevy_sequence::combinators::delay::run_after;
-use std::time::Duration;
 
 use crate::cutscene::fragments::IntoBox;
+use crate::cutscene::script::DialogueAppExt;
 
 pub mod dark_home;
 pub mod park_man;
 pub mod tea;
 pub mod visitor;
 
-/// This is mainly useful for quick testing at the moment.
+/// Registers the hooks that the cutscene scripts call for.
 pub struct CutscenePlugin;
 
 impl Plugin for CutscenePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, move |mut commands: Commands| {
-            run_after(
-                Duration::from_secs(2),
-                |mut commands: Commands| {
-                    // tea::tea_cutscene().spawn_box(&mut commands);
-                },
-                &mut commands,
-            );
-        });
+        app.register_dialogue_hook("fade_out_music", tea::fade_out_music(3.5))
+            .register_dialogue_hook("luna_theme", tea::luna_theme)
+            .register_dialogue_hook("laugh", tea::laugh)
+            .register_dialogue_hook("slow_luna_theme", dark_home::slow_luna_theme)
+            .register_dialogue_hook("lower_pitch", dark_home::lower_pitch);
     }
 }
+
+/// Every cutscene [`play`] knows by name.
+pub const NAMES: &[&str] = &[
+    "tea",
+    "visitor",
+    "park",
+    "park_man_one",
+    "park_man_two",
+    "sturgeon",
+    "shadow_1",
+    "shadow_2",
+    "shadow_3",
+    "shadow_4",
+    "shadow_5",
+    "shadow_6",
+    "shadow_7",
+    "shadow_8",
+    "shadow_9",
+    "final",
+];
+
+/// Play the cutscene called `name`, returning `false` if there isn't one.
+pub fn play(name: &str, commands: &mut Commands) -> bool {
+    match name {
+        "tea" => tea::tea_cutscene().spawn_box(commands),
+        "visitor" => visitor::visitor().spawn_box(commands),
+        "park" => park_man::park().spawn_box(commands),
+        "park_man_one" => park_man::park_man_one().spawn_box(commands),
+        "park_man_two" => park_man::park_man_two().spawn_box(commands),
+        "sturgeon" => dark_home::sturgeon().spawn_box(commands),
+        "final" => dark_home::final_cutscene().spawn_box(commands),
+        _ => match shadow_step(name) {
+            Some(step) => dark_home::shadow(step).spawn_box(commands),
+            None => return false,
+        },
+    }
+    true
+}
+
+/// The step for `shadow_1` through `shadow_9`.
+fn shadow_step(name: &str) -> Option<usize> {
+    let number: usize = name.strip_prefix("shadow_")?.parse().ok()?;
+    number
+        .checked_sub(1)
+        .filter(|step| *step < dark_home::SHADOW_SCRIPTS.len())
+}
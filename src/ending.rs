@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
+use bevy_tween::interpolate::sprite_color;
+use bevy_tween::prelude::{AnimationBuilderExt, EaseKind};
+use bevy_tween::tween::IntoTarget;
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+use crate::reset::ResetWorld;
+use crate::save::{data_path, read_data, write_data};
+use crate::sequence::run_after;
+
+pub struct EndingPlugin;
+
+impl Plugin for EndingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Ending), fade_out)
+            .add_systems(
+                OnExit(GameState::Ending),
+                crate::despawn_entities::<With<Ending>>,
+            );
+    }
+}
+
+/// The completion file, in the game's data directory.
+const COMPLETION_FILE: &str = "completion.ron";
+
+/// How long the screen takes to fade to black.
+const FADE_SECS: f32 = 3.0;
+
+/// How long the ending card stays up before the credits.
+const CARD_SECS: f32 = 6.0;
+
+/// Everything spawned for the ending, removed when it's over.
+#[derive(Component)]
+struct Ending;
+
+/// How many times the story has been finished on this machine.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Completion {
+    pub endings: u32,
+}
+
+impl Completion {
+    pub fn load() -> Self {
+        let path = data_path(COMPLETION_FILE);
+        if !path.exists() {
+            return Self::default();
+        }
+
+        read_data(&path)
+            .and_then(|data| Ok(ron::from_str(&data)?))
+            .unwrap_or_else(|e| {
+                error!("failed to read completion from {}: {e}", path.display());
+                Self::default()
+            })
+    }
+
+    fn record() -> Result {
+        let mut completion = Self::load();
+        completion.endings += 1;
+
+        let path = data_path(COMPLETION_FILE);
+        write_data(&path, &ron::to_string(&completion)?)?;
+        info!(
+            "recorded ending {} to {}",
+            completion.endings,
+            path.display()
+        );
+
+        Ok(())
+    }
+}
+
+fn fade_out(mut commands: Commands) {
+    let fade = commands
+        .spawn((
+            Ending,
+            HIGH_RES_LAYER,
+            Sprite::from_color(Color::NONE, Vec2::new(crate::WIDTH, crate::HEIGHT)),
+            Transform::from_xyz(0., 0., 999.).with_scale(Vec3::splat(crate::RESOLUTION_SCALE)),
+        ))
+        .id();
+
+    commands.entity(fade).animation().insert_tween_here(
+        Duration::from_secs_f32(FADE_SECS),
+        EaseKind::QuadraticIn,
+        fade.into_target()
+            .with(sprite_color(Color::BLACK.with_alpha(0.0), Color::BLACK)),
+    );
+
+    run_after(
+        Duration::from_secs_f32(FADE_SECS),
+        |mut commands: Commands, server: Res<AssetServer>| {
+            commands.send_event(ResetWorld);
+
+            if let Err(e) = Completion::record() {
+                error!("failed to record completion: {e}");
+            }
+
+            commands.spawn((
+                Ending,
+                HIGH_RES_LAYER,
+                Text2d::new("Time Marches On"),
+                TextFont {
+                    font: server.load("fonts/raster-forge.ttf"),
+                    font_size: 64.,
+                    ..Default::default()
+                },
+                Transform::from_xyz(0., 0., 1000.),
+            ));
+
+            // This frame's reset would drop our own `run_after`.
+            bevy_sequence::combinators::delay::run_after(
+                Duration::from_secs_f32(CARD_SECS),
+                |mut commands: Commands| commands.set_state(GameState::Credits),
+                &mut commands,
+            );
+        },
+        &mut commands,
+    );
+}
@@ -0,0 +1,140 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use bevy_sequence::prelude::IntoFragment;
+use serde::{Deserialize, Serialize};
+
+use crate::reset::ResetAppExt;
+use crate::save::{Persistent, SaveAppExt};
+use crate::sequence::{ObserverSequence, con};
+
+pub struct FlagsPlugin;
+
+impl Plugin for FlagsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StoryFlags>()
+            .register_persistent::<StoryFlags>()
+            .reset_resource::<StoryFlags>()
+            .add_systems(PostUpdate, trigger_flag_changes);
+    }
+}
+
+/// Story progress, keyed by name.
+///
+/// Every flag is an integer so the same store covers both booleans
+/// (zero or non-zero) and counters. Unknown flags read as zero.
+///
+/// Flag names are declared as constants next to the code that owns them,
+/// e.g. `TEA_DONE` in `levels::tea`.
+#[derive(Debug, Default, Clone, Resource, Serialize, Deserialize)]
+pub struct StoryFlags {
+    values: BTreeMap<String, i64>,
+    #[serde(skip)]
+    changes: Vec<FlagChanged>,
+}
+
+impl StoryFlags {
+    pub fn get(&self, name: &str) -> i64 {
+        self.values.get(name).copied().unwrap_or_default()
+    }
+
+    pub fn is_set(&self, name: &str) -> bool {
+        self.get(name) != 0
+    }
+
+    pub fn set(&mut self, name: &str) {
+        self.set_value(name, 1);
+    }
+
+    /// Add one to a counter, returning the new value.
+    pub fn increment(&mut self, name: &str) -> i64 {
+        let value = self.get(name) + 1;
+        self.set_value(name, value);
+        value
+    }
+
+    pub fn set_value(&mut self, name: &str, value: i64) {
+        let previous = self.get(name);
+        if previous == value {
+            return;
+        }
+
+        if value == 0 {
+            self.values.remove(name);
+        } else {
+            self.values.insert(name.to_string(), value);
+        }
+
+        self.changes.push(FlagChanged {
+            name: name.to_string(),
+            previous,
+            value,
+        });
+    }
+}
+
+/// Flags already at the given values, as if the story had set them.
+///
+/// No [`FlagChanged`] events are triggered for these.
+impl<'a> FromIterator<(&'a str, i64)> for StoryFlags {
+    fn from_iter<T: IntoIterator<Item = (&'a str, i64)>>(iter: T) -> Self {
+        Self {
+            values: iter
+                .into_iter()
+                .filter(|(_, value)| *value != 0)
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+            changes: Vec::new(),
+        }
+    }
+}
+
+impl Persistent for StoryFlags {
+    const KEY: &'static str = "flags";
+
+    fn capture(world: &mut World) -> Option<Self> {
+        world.get_resource::<Self>().cloned()
+    }
+
+    fn restore(self, world: &mut World) {
+        world.insert_resource(StoryFlags {
+            values: self.values,
+            changes: Vec::new(),
+        });
+    }
+}
+
+/// Triggered globally whenever a flag's value changes.
+#[derive(Debug, Clone, Event)]
+pub struct FlagChanged {
+    pub name: String,
+    pub previous: i64,
+    pub value: i64,
+}
+
+fn trigger_flag_changes(mut flags: ResMut<StoryFlags>, mut commands: Commands) {
+    if flags.changes.is_empty() {
+        return;
+    }
+
+    for change in flags.changes.drain(..) {
+        debug!(
+            "flag `{}`: {} -> {}",
+            change.name, change.previous, change.value
+        );
+        commands.trigger(change);
+    }
+}
+
+/// An [`ObserverSequence`] condition that completes when `name` becomes set.
+///
+/// Only changes made after the condition starts are observed.
+pub fn on_flag(name: &'static str) -> impl IntoFragment<ObserverSequence> {
+    on_flag_value(name, 1)
+}
+
+/// An [`ObserverSequence`] condition that completes when the counter `name`
+/// reaches `value`.
+pub fn on_flag_value(name: &'static str, value: i64) -> impl IntoFragment<ObserverSequence> {
+    con(move |trigger: Trigger<FlagChanged>| trigger.name == name && trigger.value >= value)
+}
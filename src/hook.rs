@@ -4,13 +4,14 @@ use bevy::prelude::*;
 use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
 use bevy_seedling::prelude::{RepeatMode, Volume};
 use bevy_seedling::sample::SamplePlayer;
-use bevy_sequence::combinators::delay::run_after;
 use bevy_tween::interpolate::sprite_color;
 use bevy_tween::prelude::{AnimationBuilderExt, EaseKind};
 use bevy_tween::tween::IntoTarget;
 
 use crate::GameState;
 use crate::animation::{AnimationAppExt, AnimationSprite};
+use crate::reset::ResetAppExt;
+use crate::sequence::run_after;
 
 pub struct HookPlugin;
 
@@ -26,6 +27,7 @@ impl Plugin for HookPlugin {
                 None,
             ),
         )
+        .despawn_on_reset::<Hook>()
         .add_systems(OnEnter(GameState::Hook), spawn);
     }
 }
@@ -2,8 +2,10 @@ use avian2d::prelude::*;
 use bevy::prelude::*;
 use bevy_enhanced_input::prelude::*;
 
+use crate::controls::{Control, Controls};
+use crate::inventory::item_use::{HeldItem, use_held_item};
 use crate::player::{PlayerCollider, PlayerContext};
-use crate::textbox::{TextBlurb, TextboxEvent};
+use crate::textbox::{TextBlurb, TextboxEvent, TextboxPriority};
 
 pub struct InteractionPlugin;
 
@@ -19,7 +21,9 @@ impl Plugin for InteractionPlugin {
 
 /// Checks for interactions from the player.
 ///
-/// Inserts `Interacted` for one frame.
+/// Inserts `Interacted` for one frame, or triggers
+/// [`ItemUsedOn`](crate::inventory::item_use::ItemUsedOn) if the player
+/// is holding an item.
 #[derive(Default, Component)]
 #[require(Sensor, CollidingEntities)]
 pub struct Interactable;
@@ -35,6 +39,7 @@ fn interactable(
     mut commands: Commands,
     player: Single<(Entity, &Transform), With<PlayerCollider>>,
     interactables: Query<(Entity, &GlobalTransform, &CollidingEntities), With<Interactable>>,
+    held: Res<HeldItem>,
 ) {
     let (player, transform) = player.into_inner();
 
@@ -53,8 +58,15 @@ fn interactable(
             )
     });
 
-    if let Some((entity, _, _)) = interactions.first() {
-        commands.entity(*entity).insert(Interacted);
+    let nearest = interactions.first().map(|(entity, _, _)| *entity);
+
+    if held.0.is_some() {
+        commands.queue(use_held_item(nearest));
+        return;
+    }
+
+    if let Some(entity) = nearest {
+        commands.entity(entity).insert(Interacted);
     }
 }
 
@@ -71,17 +83,13 @@ pub struct InteractAction;
 fn bind(
     trigger: Trigger<Binding<PlayerContext>>,
     mut actions: Query<&mut Actions<PlayerContext>>,
+    controls: Res<Controls>,
 ) -> Result {
     let mut actions = actions.get_mut(trigger.target())?;
 
     actions
         .bind::<InteractAction>()
-        .to((
-            KeyCode::KeyJ,
-            KeyCode::KeyE,
-            KeyCode::Space,
-            GamepadButton::South,
-        ))
+        .to(controls.inputs(Control::Interact))
         .with_conditions(JustPress::default());
 
     Ok(())
@@ -105,7 +113,12 @@ fn interact(
     interactions: Query<(&crate::world::Interaction, &GlobalTransform)>,
     collisions: Collisions,
     mut writer: EventWriter<TextboxEvent>,
+    held: Res<HeldItem>,
 ) -> Result {
+    if held.0.is_some() {
+        return Ok(());
+    }
+
     let target = trigger.target();
     let interactor = interactor.get(target)?;
     let interactor_translation = interactor.translation().xy();
@@ -137,9 +150,10 @@ fn interact(
         return Ok(());
     };
 
-    writer.write(TextboxEvent::section(TextBlurb::main_character(
-        interactor.flavor.clone(),
-    )));
+    writer.write(
+        TextboxEvent::section(TextBlurb::main_character(interactor.flavor.clone()))
+            .with_priority(TextboxPriority::Flavor),
+    );
 
     Ok(())
 }
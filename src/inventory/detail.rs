@@ -0,0 +1,125 @@
+use bevy::{input_focus::InputFocus, prelude::*};
+
+use super::{InventorySlot, item::InventoryItem, item_use::ItemAction};
+use crate::PlayingState;
+
+pub struct DetailPlugin;
+
+impl Plugin for DetailPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            update_detail
+                .run_if(in_state(PlayingState::Paused).and(resource_changed::<InputFocus>)),
+        );
+    }
+}
+
+const EMPTY_POCKETS: &str = "Your pockets are empty.";
+
+#[derive(Component)]
+struct DetailIcon;
+
+#[derive(Component)]
+struct DetailDescription;
+
+#[derive(Component)]
+struct DetailExamine;
+
+/// The panel beside the item grid describing the focused item.
+pub(super) fn detail_panel(server: &AssetServer) -> impl Bundle {
+    (
+        BackgroundColor(Color::BLACK),
+        BorderColor(Color::WHITE),
+        Node {
+            width: Val::Px(400.0),
+            height: Val::Px(508.0),
+            padding: UiRect::all(Val::Px(25.0)),
+            border: UiRect::all(Val::Px(4.0)),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(25.0),
+            ..default()
+        },
+        children![
+            (
+                DetailIcon,
+                ImageNode::default(),
+                Node {
+                    display: Display::None,
+                    width: Val::Px(96.0),
+                    height: Val::Px(96.0),
+                    ..default()
+                },
+            ),
+            (
+                DetailDescription,
+                Text::new(EMPTY_POCKETS),
+                TextLayout {
+                    justify: JustifyText::Center,
+                    ..default()
+                },
+                TextFont {
+                    font: server.load("fonts/raster-forge.ttf"),
+                    ..Default::default()
+                },
+            ),
+            (
+                DetailExamine,
+                ImageNode::default(),
+                Node {
+                    display: Display::None,
+                    max_width: Val::Percent(100.0),
+                    flex_grow: 1.0,
+                    ..default()
+                },
+            ),
+        ],
+    )
+}
+
+fn update_detail(
+    input_focus: Res<InputFocus>,
+    slots: Query<&InventorySlot>,
+    actions: Query<&ItemAction>,
+    items: Query<&InventoryItem>,
+    mut description: Single<&mut Text, With<DetailDescription>>,
+    mut icon: Single<(&mut ImageNode, &mut Node), (With<DetailIcon>, Without<DetailExamine>)>,
+    mut examine: Single<(&mut ImageNode, &mut Node), (With<DetailExamine>, Without<DetailIcon>)>,
+    server: Res<AssetServer>,
+) {
+    let Some(focused) = input_focus.0 else {
+        return;
+    };
+
+    // The action row keeps showing the item it was opened for.
+    let item = match actions.get(focused) {
+        Ok(ItemAction::Use(item)) => Some(*item),
+        Ok(ItemAction::Back(slot)) => slots.get(*slot).ok().map(|slot| slot.0),
+        Err(_) => slots.get(focused).ok().map(|slot| slot.0),
+    };
+    let Some(item) = item.and_then(|item| items.get(item).ok()) else {
+        return;
+    };
+
+    description.0 = item.description.clone();
+    show_image(&mut icon.0, &mut icon.1, item.icon.as_deref(), &server);
+    show_image(
+        &mut examine.0,
+        &mut examine.1,
+        item.examine.as_deref(),
+        &server,
+    );
+}
+
+fn show_image(image: &mut ImageNode, node: &mut Node, path: Option<&str>, server: &AssetServer) {
+    match path {
+        Some(path) => {
+            image.image = server.load(path.to_string());
+            node.display = Display::Flex;
+        }
+        None => {
+            node.display = Display::None;
+        }
+    }
+}
@@ -13,7 +13,11 @@ use bevy::{
 use bevy_enhanced_input::prelude::*;
 use bevy_seedling::{prelude::Volume, sample::SamplePlayer};
 
-use crate::{PlayingState, textbox::glyph_sample};
+use crate::{
+    PlayingState,
+    controls::{Control, Controls},
+    textbox::glyph_sample,
+};
 
 #[derive(InputContext)]
 pub struct InventoryContext;
@@ -34,28 +38,18 @@ pub struct UnpauseAction;
 pub fn bind(
     trigger: Trigger<Binding<InventoryContext>>,
     mut actions: Query<&mut Actions<InventoryContext>>,
+    controls: Res<Controls>,
 ) -> Result {
     let mut actions = actions.get_mut(trigger.target())?;
 
     actions
         .bind::<UnpauseAction>()
-        .to((
-            KeyCode::Escape,
-            KeyCode::Tab,
-            KeyCode::KeyI,
-            GamepadButton::Start,
-        ))
+        .to(controls.inputs(Control::Unpause))
         .with_conditions(JustPress::default());
 
     actions
         .bind::<InteractAction>()
-        .to((
-            KeyCode::Enter,
-            KeyCode::Space,
-            KeyCode::KeyE,
-            KeyCode::KeyJ,
-            GamepadButton::South,
-        ))
+        .to(controls.inputs(Control::Select))
         .with_conditions(JustPress::default());
 
     actions
@@ -122,9 +116,15 @@ pub fn navigate(
 
 pub fn unpause(
     trigger: Trigger<Fired<UnpauseAction>>,
+    inventory: Query<(), With<super::InventoryRoot>>,
     mut commands: Commands,
     mut next_state: ResMut<NextState<PlayingState>>,
 ) -> Result {
+    // The main menu shares this context, but there's nothing to unpause there.
+    if inventory.get(trigger.target()).is_err() {
+        return Ok(());
+    }
+
     // remove player context, enter paused state
     commands
         .entity(trigger.target())
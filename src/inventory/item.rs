@@ -1,32 +1,83 @@
 use bevy::prelude::*;
 use bevy_seedling::sample::SamplePlayer;
+use serde::{Deserialize, Serialize};
+
+use crate::reset::ResetAppExt;
+use crate::save::{Persistent, SaveAppExt};
 
 pub struct ItemsPlugin;
 
 impl Plugin for ItemsPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<ItemPickupEvent>()
-            .add_systems(Startup, |mut commands: Commands| {
-                // commands.spawn(Inventory);
-
-                commands.spawn((
-                    Inventory,
-                    children![
-                        InventoryItem {
-                            name: "Pencil".into(),
-                            description: "You keep it on you at all times.".into(),
-                        },
-                        InventoryItem {
-                            name: "Note (1)".into(),
-                            description: "A note.".into(),
-                        },
-                    ],
-                ));
-            })
+            .register_persistent::<SavedInventory>()
+            .despawn_on_reset::<Inventory>()
+            .on_reset(spawn_inventory)
+            .add_systems(Startup, spawn_inventory)
             .add_systems(PostUpdate, add_inventory_item);
     }
 }
 
+fn spawn_inventory(mut commands: Commands) {
+    commands.spawn((Inventory, children![pencil()]));
+}
+
+/// The one item the player always starts with.
+pub fn pencil() -> InventoryItem {
+    InventoryItem {
+        name: "Pencil".into(),
+        description: "You keep it on you at all times.".into(),
+        ..Default::default()
+    }
+}
+
+pub const PILLS: &str = "Pills";
+
+/// The pills that can be thrown out in the trash.
+pub fn pills() -> InventoryItem {
+    InventoryItem {
+        name: PILLS.into(),
+        description: "Half full bottle of pills.".into(),
+        ..Default::default()
+    }
+}
+
+pub const KEY: &str = "Key";
+
+/// Opens Luna's door.
+pub fn key() -> InventoryItem {
+    InventoryItem {
+        name: KEY.into(),
+        description: "This seems important.".into(),
+        ..Default::default()
+    }
+}
+
+/// Look up an item by name, ignoring case.
+pub fn item(name: &str) -> Option<InventoryItem> {
+    [pencil(), pills(), key()]
+        .into_iter()
+        .find(|item| item.name.eq_ignore_ascii_case(name))
+}
+
+/// Replace everything in the inventory with `items`.
+pub fn replace_inventory(world: &mut World, items: impl IntoIterator<Item = InventoryItem>) {
+    let Ok(inventory) = world
+        .query_filtered::<Entity, With<Inventory>>()
+        .single(world)
+    else {
+        return;
+    };
+
+    let mut inventory = world.entity_mut(inventory);
+    inventory.despawn_related::<Children>();
+    inventory.with_children(|parent| {
+        for item in items {
+            parent.spawn(item);
+        }
+    });
+}
+
 /// The player's inventory.
 ///
 /// Inventory items are stored as children of this entity.
@@ -34,10 +85,64 @@ impl Plugin for ItemsPlugin {
 pub struct Inventory;
 
 /// Minimal information about an inventory item.
-#[derive(Debug, Component)]
+#[derive(Debug, Default, Component)]
 pub struct InventoryItem {
     pub name: String,
     pub description: String,
+    /// A small image shown next to the description, relative to the assets directory.
+    pub icon: Option<String>,
+    /// A larger image shown when examining the item, relative to the assets directory.
+    pub examine: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedItem {
+    name: String,
+    description: String,
+    #[serde(default)]
+    icon: Option<String>,
+    #[serde(default)]
+    examine: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedInventory(Vec<SavedItem>);
+
+impl Persistent for SavedInventory {
+    const KEY: &'static str = "inventory";
+
+    fn capture(world: &mut World) -> Option<Self> {
+        let inventory = world
+            .query_filtered::<Option<&Children>, With<Inventory>>()
+            .single(world)
+            .ok()?;
+
+        let items = inventory
+            .iter()
+            .flat_map(|children| children.iter())
+            .filter_map(|item| world.get::<InventoryItem>(item))
+            .map(|item| SavedItem {
+                name: item.name.clone(),
+                description: item.description.clone(),
+                icon: item.icon.clone(),
+                examine: item.examine.clone(),
+            })
+            .collect();
+
+        Some(Self(items))
+    }
+
+    fn restore(self, world: &mut World) {
+        replace_inventory(
+            world,
+            self.0.into_iter().map(|item| InventoryItem {
+                name: item.name,
+                description: item.description,
+                icon: item.icon,
+                examine: item.examine,
+            }),
+        );
+    }
 }
 
 /// An event indicating the provided item entity was picked up.
@@ -51,6 +156,8 @@ pub struct InventoryItem {
 ///     let item = commands.spawn(InventoryItem {
 ///         name: "My item".into(),
 ///         description: "My description...".into(),
+///         icon: Some("textures/items/my-item.png".into()),
+///         ..Default::default()
 ///     }).id();
 ///
 ///     writer.write(ItemPickupEvent(item));
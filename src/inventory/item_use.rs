@@ -0,0 +1,269 @@
+use bevy::{
+    input_focus::{InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+
+use super::{InventoryColumn, InventorySlot, InvertOnFocus, ResetTimer, item::InventoryItem};
+use crate::{
+    PlayingState,
+    reset::ResetAppExt,
+    textbox::{TextBlurb, TextboxEvent},
+};
+
+pub struct ItemUsePlugin;
+
+impl Plugin for ItemUsePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HeldItem>()
+            .reset_resource::<HeldItem>()
+            .add_systems(OnEnter(PlayingState::Paused), drop_held_item)
+            .add_systems(Update, held_item_hint.run_if(resource_changed::<HeldItem>))
+            .add_observer(open_actions)
+            .add_observer(choose_action);
+    }
+}
+
+/// The item the player has chosen to use from the pause menu.
+///
+/// The next interaction with an [`Interactable`](crate::interactions::Interactable)
+/// uses this item on it instead.
+#[derive(Debug, Default, Resource)]
+pub struct HeldItem(pub Option<Entity>);
+
+/// Triggered on an interactable when the player uses an item on it.
+///
+/// Observers decide what happens by calling [`ItemUsedOn::accept`],
+/// [`ItemUsedOn::reject`], or [`ItemUsedOn::consume`]. If nobody responds,
+/// the player is told the item does nothing.
+///
+/// ```ignore
+/// fn unlock(mut trigger: Trigger<ItemUsedOn>, items: Query<&InventoryItem>) {
+///     if items.get(trigger.item).is_ok_and(|item| item.name == "Key") {
+///         trigger.event_mut().consume();
+///     } else {
+///         trigger.event_mut().reject("It won't fit.");
+///     }
+/// }
+/// ```
+#[derive(Debug, Event)]
+pub struct ItemUsedOn {
+    pub item: Entity,
+    pub target: Entity,
+    response: ItemResponse,
+}
+
+#[derive(Debug, Default)]
+enum ItemResponse {
+    #[default]
+    Ignored,
+    Accepted,
+    Rejected(String),
+    Consumed,
+}
+
+impl ItemUsedOn {
+    /// The item worked and stays in the inventory.
+    pub fn accept(&mut self) {
+        self.response = ItemResponse::Accepted;
+    }
+
+    /// The item didn't work, with a line explaining why.
+    pub fn reject(&mut self, line: impl Into<String>) {
+        self.response = ItemResponse::Rejected(line.into());
+    }
+
+    /// The item worked and is used up.
+    pub fn consume(&mut self) {
+        self.response = ItemResponse::Consumed;
+    }
+}
+
+const NOTHING_HAPPENS: &str = "That doesn't seem to do anything.";
+
+/// Use the held item on `target`, or tell the player there's nothing to use it on.
+pub fn use_held_item(target: Option<Entity>) -> impl FnOnce(&mut World) + Send + 'static {
+    move |world: &mut World| {
+        let Some(item) = world.resource_mut::<HeldItem>().0.take() else {
+            return;
+        };
+
+        let Some(target) = target else {
+            world.send_event(TextboxEvent::section(TextBlurb::narrator(
+                "There's nothing to use it on here.",
+            )));
+            return;
+        };
+
+        let mut event = ItemUsedOn {
+            item,
+            target,
+            response: ItemResponse::Ignored,
+        };
+        world.trigger_targets_ref(&mut event, target);
+
+        match event.response {
+            ItemResponse::Ignored => {
+                world.send_event(TextboxEvent::section(TextBlurb::narrator(NOTHING_HAPPENS)));
+            }
+            ItemResponse::Rejected(line) => {
+                world.send_event(TextboxEvent::section(TextBlurb::narrator(line)));
+            }
+            ItemResponse::Accepted => {}
+            ItemResponse::Consumed => {
+                if let Ok(item) = world.get_entity_mut(item) {
+                    item.despawn();
+                }
+            }
+        }
+    }
+}
+
+fn drop_held_item(mut held: ResMut<HeldItem>) {
+    held.0 = None;
+}
+
+#[derive(Component)]
+struct HeldItemHint;
+
+fn held_item_hint(
+    held: Res<HeldItem>,
+    hints: Query<Entity, With<HeldItemHint>>,
+    items: Query<&InventoryItem>,
+    server: Res<AssetServer>,
+    mut commands: Commands,
+) {
+    for hint in hints.iter() {
+        commands.entity(hint).despawn();
+    }
+
+    let Some(item) = held.0.and_then(|item| items.get(item).ok()) else {
+        return;
+    };
+
+    commands.spawn((
+        HeldItemHint,
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(24.0),
+            left: Val::Px(24.0),
+            ..default()
+        },
+        children![(
+            Text::new(format!("Using: {}", item.name)),
+            TextFont {
+                font: server.load("fonts/raster-forge.ttf"),
+                ..Default::default()
+            },
+        )],
+    ));
+}
+
+/// A button in the action row shown after choosing an item in the pause menu.
+#[derive(Component)]
+pub(super) enum ItemAction {
+    Use(Entity),
+    Back(Entity),
+}
+
+#[derive(Component)]
+struct ItemActions;
+
+fn open_actions(
+    trigger: Trigger<Pointer<Click>>,
+    slots: Query<&InventorySlot>,
+    existing: Query<(), With<ItemActions>>,
+    column: Single<Entity, With<InventoryColumn>>,
+    mut directional_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    mut commands: Commands,
+    server: Res<AssetServer>,
+) {
+    let slot = trigger.target();
+    let Ok(InventorySlot(item)) = slots.get(slot) else {
+        return;
+    };
+
+    if !existing.is_empty() {
+        return;
+    }
+
+    let use_button = commands
+        .spawn(action_button("Use", ItemAction::Use(*item), &server))
+        .id();
+    let back_button = commands
+        .spawn(action_button("Back", ItemAction::Back(slot), &server))
+        .id();
+
+    commands
+        .spawn((
+            ItemActions,
+            BackgroundColor(Color::BLACK),
+            BorderColor(Color::WHITE),
+            Node {
+                width: Val::Px(708.0),
+                height: Val::Px(75.0),
+                border: UiRect::all(Val::Px(4.0)),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::SpaceAround,
+                ..default()
+            },
+            ChildOf(*column),
+        ))
+        .add_children(&[use_button, back_button]);
+
+    directional_nav_map.add_looping_edges(&[use_button, back_button], CompassOctant::East);
+    input_focus.set(use_button);
+}
+
+fn action_button(label: &'static str, action: ItemAction, server: &AssetServer) -> impl Bundle {
+    (
+        Button,
+        action,
+        Node {
+            width: Val::Px(200.0),
+            height: Val::Px(60.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        ResetTimer::default(),
+        BackgroundColor::from(Color::NONE),
+        children![(
+            Text::new(label),
+            TextFont {
+                font: server.load("fonts/raster-forge.ttf"),
+                ..Default::default()
+            },
+            InvertOnFocus,
+        )],
+    )
+}
+
+fn choose_action(
+    trigger: Trigger<Pointer<Click>>,
+    actions: Query<&ItemAction>,
+    row: Single<(Entity, &Children), With<ItemActions>>,
+    mut held: ResMut<HeldItem>,
+    mut next_state: ResMut<NextState<PlayingState>>,
+    mut directional_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    mut commands: Commands,
+) {
+    let Ok(action) = actions.get(trigger.target()) else {
+        return;
+    };
+
+    match action {
+        ItemAction::Use(item) => {
+            held.0 = Some(*item);
+            next_state.set(PlayingState::Playing);
+        }
+        ItemAction::Back(slot) => {
+            let (row, buttons) = row.into_inner();
+            directional_nav_map.remove_multiple(buttons.iter().collect());
+            commands.entity(row).despawn();
+            input_focus.set(*slot);
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use bevy::{
+    input_focus::{InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+
+use super::{InventoryRoot, InvertOnFocus, ResetTimer};
+use crate::{
+    PlayingState,
+    notes::{Journal, ReadNoteEvent},
+};
+
+pub struct JournalPlugin;
+
+impl Plugin for JournalPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, show_page.run_if(in_state(PlayingState::Paused)))
+            .add_observer(open_journal)
+            .add_observer(choose_action);
+    }
+}
+
+/// The header button that opens the journal.
+#[derive(Component)]
+pub(super) struct JournalButton;
+
+/// A button along the bottom of the open journal.
+#[derive(Component)]
+pub(super) enum JournalAction {
+    Previous,
+    Read,
+    Next,
+    Back,
+}
+
+/// The open journal, showing the note at `index`.
+#[derive(Component)]
+struct JournalPage {
+    index: usize,
+}
+
+#[derive(Component)]
+struct JournalPreview;
+
+#[derive(Component)]
+struct JournalPageLabel;
+
+pub(super) fn journal_button(server: &AssetServer) -> impl Bundle {
+    (
+        Button,
+        JournalButton,
+        Node {
+            width: Val::Px(200.0),
+            height: Val::Px(60.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        ResetTimer::default(),
+        BackgroundColor::from(Color::NONE),
+        children![(
+            Text::new("Journal"),
+            TextFont {
+                font: server.load("fonts/raster-forge.ttf"),
+                ..Default::default()
+            },
+            InvertOnFocus,
+        )],
+    )
+}
+
+fn open_journal(
+    trigger: Trigger<Pointer<Click>>,
+    buttons: Query<(), With<JournalButton>>,
+    existing: Query<(), With<JournalPage>>,
+    root: Single<Entity, With<InventoryRoot>>,
+    journal: Res<Journal>,
+    mut directional_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    mut commands: Commands,
+    server: Res<AssetServer>,
+) {
+    if buttons.get(trigger.target()).is_err() || !existing.is_empty() {
+        return;
+    }
+
+    let actions = [
+        ("Previous", JournalAction::Previous),
+        ("Read", JournalAction::Read),
+        ("Next", JournalAction::Next),
+        ("Back", JournalAction::Back),
+    ]
+    .map(|(label, action)| commands.spawn(action_button(label, action, &server)).id());
+
+    let action_row = commands
+        .spawn(Node {
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::SpaceAround,
+            ..default()
+        })
+        .add_children(&actions)
+        .id();
+
+    commands
+        .spawn((
+            JournalPage {
+                // Open on the most recent note.
+                index: journal.notes.len().saturating_sub(1),
+            },
+            BackgroundColor(Color::BLACK),
+            BorderColor(Color::WHITE),
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                padding: UiRect::all(Val::Px(25.0)),
+                border: UiRect::all(Val::Px(4.0)),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(25.0),
+                ..default()
+            },
+            ChildOf(*root),
+            children![
+                (
+                    JournalPreview,
+                    ImageNode::default(),
+                    Node {
+                        height: Val::Px(380.0),
+                        ..default()
+                    },
+                ),
+                (
+                    JournalPageLabel,
+                    Text::default(),
+                    TextFont {
+                        font: server.load("fonts/raster-forge.ttf"),
+                        ..Default::default()
+                    },
+                ),
+            ],
+        ))
+        .add_child(action_row);
+
+    directional_nav_map.add_looping_edges(&actions, CompassOctant::East);
+    input_focus.set(if journal.notes.is_empty() {
+        actions[3]
+    } else {
+        actions[1]
+    });
+}
+
+fn action_button(label: &'static str, action: JournalAction, server: &AssetServer) -> impl Bundle {
+    (
+        Button,
+        action,
+        Node {
+            width: Val::Px(200.0),
+            height: Val::Px(60.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        ResetTimer::default(),
+        BackgroundColor::from(Color::NONE),
+        children![(
+            Text::new(label),
+            TextFont {
+                font: server.load("fonts/raster-forge.ttf"),
+                ..Default::default()
+            },
+            InvertOnFocus,
+        )],
+    )
+}
+
+fn show_page(
+    page: Single<&JournalPage, Changed<JournalPage>>,
+    journal: Res<Journal>,
+    mut preview: Single<(&mut ImageNode, &mut Node), With<JournalPreview>>,
+    mut label: Single<&mut Text, With<JournalPageLabel>>,
+    server: Res<AssetServer>,
+) {
+    match journal.notes.get(page.index) {
+        Some(note) => {
+            preview.0.image = server.load(format!("textures/notes/{note}"));
+            preview.1.display = Display::Flex;
+            label.0 = format!("{} / {}", page.index + 1, journal.notes.len());
+        }
+        None => {
+            preview.1.display = Display::None;
+            label.0 = "You haven't found any notes.".into();
+        }
+    }
+}
+
+fn choose_action(
+    trigger: Trigger<Pointer<Click>>,
+    actions: Query<&JournalAction>,
+    buttons: Query<Entity, With<JournalAction>>,
+    page: Single<(Entity, &mut JournalPage)>,
+    journal_button: Single<Entity, With<JournalButton>>,
+    journal: Res<Journal>,
+    mut writer: EventWriter<ReadNoteEvent>,
+    mut next_state: ResMut<NextState<PlayingState>>,
+    mut directional_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    mut commands: Commands,
+) {
+    let Ok(action) = actions.get(trigger.target()) else {
+        return;
+    };
+
+    let (page_entity, mut page) = page.into_inner();
+    let last = journal.notes.len().saturating_sub(1);
+
+    match action {
+        JournalAction::Previous => {
+            page.index = page.index.saturating_sub(1);
+        }
+        JournalAction::Next => {
+            page.index = (page.index + 1).min(last);
+        }
+        JournalAction::Read => {
+            if page.index < journal.notes.len() {
+                writer.write(ReadNoteEvent(page.index));
+                next_state.set(PlayingState::Playing);
+            }
+        }
+        JournalAction::Back => {
+            directional_nav_map.remove_multiple(buttons.iter().collect());
+            commands.entity(page_entity).despawn();
+            input_focus.set(*journal_button);
+        }
+    }
+}
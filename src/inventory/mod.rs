@@ -9,10 +9,16 @@ use bevy::{
 };
 use bevy_enhanced_input::prelude::{Actions, InputContextAppExt};
 
+use crate::GameState;
 use crate::player::{InhibitRemoveEvent, Player, PlayerContext};
+use crate::reset::{ResetAppExt, ResetWorld};
+use crate::save::SaveEvent;
 
-mod input;
+mod detail;
+pub mod input;
 pub mod item;
+pub mod item_use;
+mod journal;
 mod pause;
 mod scroll;
 
@@ -27,6 +33,9 @@ impl Plugin for InventoryPlugin {
                 InputDispatchPlugin,
                 DirectionalNavigationPlugin,
                 item::ItemsPlugin,
+                item_use::ItemUsePlugin,
+                detail::DetailPlugin,
+                journal::JournalPlugin,
             ))
             // This resource is canonically used to track whether or not to render a focus indicator
             // It starts as false, but we set it to true here as we would like to see the focus indicator
@@ -47,8 +56,11 @@ impl Plugin for InventoryPlugin {
             // However, we're filtering in the observer's query to only respond to button presses
             .add_observer(universal_button_click_behavior)
             .add_observer(focus_on_hover)
+            .add_observer(click_header_button)
             .add_input_context::<input::InventoryContext>()
             .add_systems(OnEnter(crate::PlayingState::Paused), setup_ui)
+            .despawn_on_reset::<InventoryRoot>()
+            .on_reset(reset_navigation)
             .add_systems(OnExit(crate::PlayingState::Paused), teardown_ui)
             .add_observer(pause::bind)
             .add_observer(pause::pause)
@@ -63,8 +75,30 @@ impl Plugin for InventoryPlugin {
 #[derive(Component)]
 pub struct InventoryRoot;
 
+/// The column holding the header, item grid and action row.
 #[derive(Component)]
-pub struct InventorySlot;
+struct InventoryColumn;
+
+/// A button in the header, next to the journal.
+#[derive(Clone, Copy, Component)]
+enum HeaderButton {
+    Settings,
+    /// Save, reset the world, and go back to the title screen.
+    QuitToMenu,
+}
+
+impl HeaderButton {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Settings => "Settings",
+            Self::QuitToMenu => "Quit",
+        }
+    }
+}
+
+/// A button in the inventory grid for the given item.
+#[derive(Component)]
+pub struct InventorySlot(pub Entity);
 
 // This observer will be triggered whenever a button is pressed
 // In a real project, each button would also have its own unique behavior,
@@ -101,6 +135,16 @@ fn reset_button_after_interaction(
     }
 }
 
+/// The pause menu is despawned without [`teardown_ui`] when the world
+/// resets, so its navigation has to go too.
+fn reset_navigation(
+    mut directional_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+) {
+    directional_nav_map.clear();
+    input_focus.clear();
+}
+
 fn teardown_ui(
     player: Single<Entity, With<Player>>,
     inventory: Single<Entity, With<InventoryRoot>>,
@@ -135,6 +179,17 @@ fn setup_ui(
             Actions::<input::InventoryContext>::default(),
             Node {
                 margin: UiRect::AUTO,
+                column_gap: Val::Px(25.0),
+                align_items: AlignItems::Start,
+                ..default()
+            },
+        ))
+        .id();
+
+    let column_node = commands
+        .spawn((
+            InventoryColumn,
+            Node {
                 flex_direction: FlexDirection::Column,
                 row_gap: Val::Px(25.0),
                 ..default()
@@ -142,6 +197,16 @@ fn setup_ui(
         ))
         .id();
 
+    let detail_node = commands.spawn(detail::detail_panel(&server)).id();
+
+    let journal_button = commands.spawn(journal::journal_button(&server)).id();
+    let settings_button = commands
+        .spawn(header_button(HeaderButton::Settings, &server))
+        .id();
+    let quit_button = commands
+        .spawn(header_button(HeaderButton::QuitToMenu, &server))
+        .id();
+
     let header_node = commands
         .spawn((
             BackgroundColor(Color::BLACK),
@@ -163,6 +228,7 @@ fn setup_ui(
                 InvertOnFocus,
             )],
         ))
+        .add_children(&[journal_button, settings_button, quit_button])
         .id();
 
     let outer_content_node = commands
@@ -217,10 +283,13 @@ fn setup_ui(
         .entity(outer_content_node)
         .add_children(&[scroll_up, content_node, scroll_down]);
 
-    // Add the instructions and grid to the root node
+    // Add the instructions and grid to the left column, with item details beside it
     commands
-        .entity(root_node)
+        .entity(column_node)
         .add_children(&[header_node, outer_content_node]);
+    commands
+        .entity(root_node)
+        .add_children(&[column_node, detail_node]);
 
     commands.entity(content_node).add_child(grid_root_entity);
 
@@ -229,13 +298,13 @@ fn setup_ui(
     let mut button_entities: HashMap<(u16, u16), Entity> = HashMap::default();
     for row in 0..n_rows {
         for col in 0..N_COLS {
-            let Some(item) = item_iter.next() else {
+            let Some(entity) = item_iter.next() else {
                 break;
             };
-            let item = items.get(item)?;
+            let item = items.get(entity)?;
 
             let button_entity = commands
-                .spawn(inventory_slot(item.name.clone(), &server))
+                .spawn(inventory_slot(entity, item.name.clone(), &server))
                 .id();
 
             // Add the button to the grid
@@ -268,19 +337,82 @@ fn setup_ui(
         directional_nav_map.add_edges(&entities_in_column, CompassOctant::South);
     }
 
-    // When changing scenes, remember to set an initial focus!
+    // The journal button sits above the whole top row.
+    for col in 0..N_COLS {
+        if let Some(entity) = button_entities.get(&(0, col)) {
+            directional_nav_map.add_edge(*entity, journal_button, CompassOctant::North);
+        }
+    }
     if let Some(top_left_entity) = button_entities.get(&(0, 0)) {
-        input_focus.set(*top_left_entity);
+        directional_nav_map.add_edge(journal_button, *top_left_entity, CompassOctant::South);
+        directional_nav_map.add_edge(settings_button, *top_left_entity, CompassOctant::South);
+        directional_nav_map.add_edge(quit_button, *top_left_entity, CompassOctant::South);
+    }
+    directional_nav_map.add_looping_edges(
+        &[journal_button, settings_button, quit_button],
+        CompassOctant::East,
+    );
+
+    // When changing scenes, remember to set an initial focus!
+    match button_entities.get(&(0, 0)) {
+        Some(top_left_entity) => input_focus.set(*top_left_entity),
+        None => input_focus.set(journal_button),
     }
 
     Ok(())
 }
 
-fn inventory_slot(name: impl Into<String>, server: &AssetServer) -> impl Bundle {
+fn header_button(action: HeaderButton, server: &AssetServer) -> impl Bundle {
+    (
+        Button,
+        action,
+        Node {
+            width: Val::Px(160.0),
+            height: Val::Px(60.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        ResetTimer::default(),
+        BackgroundColor::from(Color::NONE),
+        children![(
+            Text::new(action.label()),
+            TextFont {
+                font: server.load("fonts/raster-forge.ttf"),
+                ..Default::default()
+            },
+            InvertOnFocus,
+        )],
+    )
+}
+
+fn click_header_button(
+    trigger: Trigger<Pointer<Click>>,
+    buttons: Query<&HeaderButton>,
+    mut save: EventWriter<SaveEvent>,
+    mut reset: EventWriter<ResetWorld>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut commands: Commands,
+) {
+    let Ok(button) = buttons.get(trigger.target()) else {
+        return;
+    };
+
+    match button {
+        HeaderButton::Settings => commands.trigger(crate::settings::OpenSettings),
+        HeaderButton::QuitToMenu => {
+            save.write_default();
+            reset.write_default();
+            next_state.set(GameState::Menu);
+        }
+    }
+}
+
+fn inventory_slot(item: Entity, name: impl Into<String>, server: &AssetServer) -> impl Bundle {
     let button_name = name.into();
     (
         Button,
-        InventorySlot,
+        InventorySlot(item),
         Node {
             width: Val::Px(200.0),
             height: Val::Px(150.0),
@@ -343,7 +475,16 @@ fn highlight_focused_element(
     // While this isn't strictly needed for the example,
     // we're demonstrating how to be a good citizen by respecting the `InputFocusVisible` resource.
     input_focus_visible: Res<InputFocusVisible>,
-    mut query: Query<(Entity, &mut BackgroundColor), With<InventorySlot>>,
+    mut query: Query<
+        (Entity, &mut BackgroundColor),
+        Or<(
+            With<InventorySlot>,
+            With<item_use::ItemAction>,
+            With<journal::JournalButton>,
+            With<journal::JournalAction>,
+            With<HeaderButton>,
+        )>,
+    >,
 ) {
     for (entity, mut background_color) in query.iter_mut() {
         if input_focus.0 == Some(entity) && input_focus_visible.0 {
@@ -356,7 +497,16 @@ fn highlight_focused_element(
 
 fn focus_on_hover(
     trigger: Trigger<Pointer<Over>>,
-    focus_targets: Query<(), With<InventorySlot>>,
+    focus_targets: Query<
+        (),
+        Or<(
+            With<InventorySlot>,
+            With<item_use::ItemAction>,
+            With<journal::JournalButton>,
+            With<journal::JournalAction>,
+            With<HeaderButton>,
+        )>,
+    >,
     mut input_focus: ResMut<InputFocus>,
 ) {
     if focus_targets.get(trigger.target()).is_err() {
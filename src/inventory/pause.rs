@@ -3,6 +3,7 @@ use bevy_enhanced_input::prelude::*;
 
 use crate::{
     PlayingState,
+    controls::{Control, Controls},
     player::{InhibitAddEvent, PlayerContext},
 };
 
@@ -13,18 +14,13 @@ pub struct PauseAction;
 pub fn bind(
     trigger: Trigger<Binding<PlayerContext>>,
     mut actions: Query<&mut Actions<PlayerContext>>,
+    controls: Res<Controls>,
 ) -> Result {
     let mut actions = actions.get_mut(trigger.target())?;
 
     actions
         .bind::<PauseAction>()
-        .to((
-            KeyCode::Escape,
-            KeyCode::Tab,
-            KeyCode::KeyI,
-            GamepadButton::Start,
-            GamepadButton::North,
-        ))
+        .to(controls.inputs(Control::Pause))
         .with_conditions(JustPress::default());
 
     Ok(())
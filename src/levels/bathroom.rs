@@ -13,7 +13,6 @@ use bevy_optix::zorder::YOrigin;
 use bevy_seedling::prelude::Volume;
 use bevy_seedling::prelude::*;
 use bevy_seedling::sample::{PlaybackSettings, SamplePlayer};
-use bevy_sequence::combinators::delay::run_after;
 use bevy_sequence::prelude::FragmentExt;
 use bevy_tween::prelude::{AnimationBuilderExt, EaseKind, Interpolator};
 use bevy_tween::tween::IntoTarget;
@@ -23,14 +22,17 @@ use rand::Rng;
 use crate::animation::{AnimationAppExt, AnimationSprite};
 use crate::audio::{MusicPool, SpatialPool};
 use crate::cutscene::fragments::IntoBox;
-use crate::cutscenes::dark_home::final_cutscene;
+use crate::cutscenes::dark_home::{SHADOW_SCRIPTS, final_cutscene, shadow};
 use crate::cutscenes::tea::{fade_in_music, fade_out_music};
+use crate::flags::StoryFlags;
 use crate::interactions::{Interactable, Interacted};
-use crate::inventory::item::{InventoryItem, ItemPickupEvent};
+use crate::inventory::item::{self, ItemPickupEvent};
 use crate::player::Player;
-use crate::{Avian, world};
+use crate::sequence::run_after;
+use crate::{Avian, GameRng, world};
 
-use super::{DoorDisabled, in_level};
+use super::door::{CrackedDoors, Door, DoorLock, unlock_door};
+use super::in_level;
 
 pub struct BathroomPlugin;
 
@@ -51,7 +53,6 @@ impl Plugin for BathroomPlugin {
             Update,
             disable_bathroom_door.run_if(in_level(world::Level2.uid())),
         )
-        .init_resource::<ScribbleDialogStep>()
         .add_systems(Avian, move_scribble)
         .add_observer(start)
         .add_observer(observe_scribbles)
@@ -103,12 +104,14 @@ struct DisabledBathroomDoor;
 
 fn disable_bathroom_door(
     mut commands: Commands,
-    doors: Query<(Entity, &world::BathroomExitDoor), Without<DisabledBathroomDoor>>,
+    mut doors: Query<
+        (Entity, &mut Door),
+        (With<world::BathroomExitDoor>, Without<DisabledBathroomDoor>),
+    >,
 ) {
-    for (entity, _) in doors.iter() {
-        commands
-            .entity(entity)
-            .insert((DoorDisabled, DisabledBathroomDoor));
+    for (entity, mut door) in doors.iter_mut() {
+        door.lock = DoorLock::locked();
+        commands.entity(entity).insert(DisabledBathroomDoor);
     }
 }
 
@@ -150,21 +153,19 @@ fn sturgeon(commands: &mut Commands) {
                  door: Query<Entity, With<DisabledBathroomDoor>>,
                  player: Single<Entity, With<Player>>| {
                     for entity in door.iter() {
-                        commands
-                            .entity(entity)
-                            .remove::<DoorDisabled>()
-                            .with_child((
-                                PlaybackSettings {
-                                    on_complete: bevy_seedling::sample::OnComplete::Remove,
-                                    ..Default::default()
-                                },
-                                SamplePlayer {
-                                    sample: server.load("audio/sfx/door-open.wav"),
-                                    //volume: Volume::Linear(1.25),
-                                    ..Default::default()
-                                },
-                                crate::audio::SpatialPool,
-                            ));
+                        commands.queue(unlock_door(entity));
+                        commands.entity(entity).with_child((
+                            PlaybackSettings {
+                                on_complete: bevy_seedling::sample::OnComplete::Remove,
+                                ..Default::default()
+                            },
+                            SamplePlayer {
+                                sample: server.load("audio/sfx/door-open.wav"),
+                                //volume: Volume::Linear(1.25),
+                                ..Default::default()
+                            },
+                            crate::audio::SpatialPool,
+                        ));
                     }
 
                     let light = commands.spawn_empty().id();
@@ -201,8 +202,11 @@ fn sturgeon(commands: &mut Commands) {
         .spawn_box(commands);
 }
 
-#[derive(Resource, Default)]
-pub struct ScribbleDialogStep(usize);
+/// How many of the shadow's scribbles have been read.
+pub const SCRIBBLES_READ: &str = "scribbles_read";
+
+/// The scribble after which the cracked door opens up.
+const CRACK_STEP: usize = 3;
 
 #[derive(Component, Default)]
 pub struct Exhausted;
@@ -221,28 +225,36 @@ pub struct Scribble;
 impl Scribble {
     fn on_insert_hook(mut world: DeferredWorld, context: HookContext) {
         world.commands().queue(move |world: &mut World| {
-            world.run_system_once(move |mut commands: Commands, server: Res<AssetServer>| {
-                let mut rng = rand::thread_rng();
-                commands.entity(context.entity).insert((
-                    AnimationSprite::repeating("textures/scribble.png", 0.2, 0..4),
-                    SpatialPool,
-                    SamplePlayer::new(server.load("audio/sfx/solo-whispers.ogg"))
-                        .looping()
-                        .with_volume(Volume::Decibels(-6.0)),
-                    PlaybackSettings {
-                        playhead: Notify::new(Playhead::Seconds(rng.gen_range(0.0..5.0))),
-                        speed: 0.9,
-                        ..Default::default()
-                    },
-                    sample_effects![(SpatialScale(Vec3::splat(1.0)), SpatialBasicNode::default(),)],
-                ));
-            })
+            world.run_system_once(
+                move |mut commands: Commands,
+                      server: Res<AssetServer>,
+                      mut rng: ResMut<GameRng>| {
+                    commands.entity(context.entity).insert((
+                        AnimationSprite::repeating("textures/scribble.png", 0.2, 0..4),
+                        SpatialPool,
+                        SamplePlayer::new(server.load("audio/sfx/solo-whispers.ogg"))
+                            .looping()
+                            .with_volume(Volume::Decibels(-6.0)),
+                        PlaybackSettings {
+                            playhead: Notify::new(Playhead::Seconds(rng.gen_range(0.0..5.0))),
+                            speed: 0.9,
+                            ..Default::default()
+                        },
+                        sample_effects![(
+                            SpatialScale(Vec3::splat(1.0)),
+                            SpatialBasicNode::default(),
+                        )],
+                    ));
+                },
+            )
         });
     }
 }
 
-fn move_scribble(mut scribbles: Query<&mut LinearVelocity, With<Scribble>>) {
-    let mut rng = rand::thread_rng();
+fn move_scribble(
+    mut scribbles: Query<&mut LinearVelocity, With<Scribble>>,
+    mut rng: ResMut<GameRng>,
+) {
     for mut scribble in &mut scribbles {
         scribble.x = rng.gen_range(-1.0..1.0) * 5.0;
         scribble.y = rng.gen_range(-1.0..1.0) * 5.0;
@@ -252,120 +264,47 @@ fn move_scribble(mut scribbles: Query<&mut LinearVelocity, With<Scribble>>) {
 fn observe_scribbles(
     trigger: Trigger<OnAdd, Interacted>,
     scribbles: Query<Has<Exhausted>, With<Scribble>>,
-    mut di: ResMut<ScribbleDialogStep>,
+    mut flags: ResMut<StoryFlags>,
     mut commands: Commands,
 ) {
     let Ok(false) = scribbles.get(trigger.target()) else {
         return;
     };
 
-    use crate::cutscenes::dark_home::*;
-    match di.0 {
-        0 => {
-            shadow_1().spawn_box(&mut commands);
-            di.0 += 1;
-            commands.entity(trigger.target()).insert(Exhausted);
-        }
-        1 => {
-            shadow_2().spawn_box(&mut commands);
-            di.0 += 1;
-            commands.entity(trigger.target()).insert(Exhausted);
-        }
-        2 => {
-            shadow_3().spawn_box(&mut commands);
-            di.0 += 1;
-            commands.entity(trigger.target()).insert(Exhausted);
-        }
-        3 => {
-            shadow_4().spawn_box(&mut commands);
-            di.0 += 1;
-            commands.entity(trigger.target()).insert(Exhausted);
+    let step = flags.get(SCRIBBLES_READ) as usize;
+    if step >= SHADOW_SCRIPTS.len() {
+        return;
+    }
 
-            run_after(
-                Duration::from_secs(1),
-                |mut commands: Commands,
-                 server: Res<AssetServer>,
-                 cracked_door: Query<(Entity, &world::CrackedSideDoor1)>,
-                 side_door: Query<(Entity, &world::SideDoor1)>| {
-                    for (entity, _) in side_door
-                        .iter()
-                        .filter(|(_, door)| door.id as usize == 8392)
-                    {
-                        commands.entity(entity).despawn();
-                    }
+    flags.increment(SCRIBBLES_READ);
+    commands.entity(trigger.target()).insert(Exhausted);
 
-                    for (entity, _) in cracked_door
-                        .iter()
-                        .filter(|(_, door)| door.id as usize == 8392)
-                    {
-                        commands.entity(entity).remove::<DoorDisabled>().insert((
-                            Visibility::Visible,
-                            // spatializing sound on door
-                            PlaybackSettings {
-                                on_complete: OnComplete::Remove,
-                                ..Default::default()
-                            },
-                            SamplePlayer {
-                                sample: server.load("audio/sfx/door-open.wav"),
-                                //volume: Volume::Linear(1.25),
-                                ..Default::default()
-                            },
-                            crate::audio::SpatialPool,
-                        ));
-                    }
+    // The last scribble leaves the key to Luna's room behind.
+    if step == SHADOW_SCRIPTS.len() - 1 {
+        shadow(step)
+            .on_end(
+                |mut commands: Commands, mut writer: EventWriter<ItemPickupEvent>| {
+                    let item = commands.spawn(item::key()).id();
+                    writer.write(ItemPickupEvent(item));
                 },
-                &mut commands,
-            );
-        }
-        4 => {
-            shadow_5().spawn_box(&mut commands);
-            di.0 += 1;
-            commands.entity(trigger.target()).insert(Exhausted);
-        }
-        5 => {
-            shadow_6().spawn_box(&mut commands);
-            di.0 += 1;
-            commands.entity(trigger.target()).insert(Exhausted);
-        }
-        6 => {
-            shadow_7().spawn_box(&mut commands);
-            di.0 += 1;
-            commands.entity(trigger.target()).insert(Exhausted);
-        }
-        7 => {
-            shadow_8().spawn_box(&mut commands);
-            di.0 += 1;
-            commands.entity(trigger.target()).insert(Exhausted);
-        }
-        8 => {
-            shadow_9()
-                .on_end(
-                    |mut commands: Commands,
-                     mut writer: EventWriter<ItemPickupEvent>,
-                     door: Single<Entity, With<LunaDoor>>| {
-                        let item = commands
-                            .spawn((InventoryItem {
-                                name: "Key".into(),
-                                description: "This seems important.".into(),
-                            },))
-                            .id();
-
-                        writer.write(ItemPickupEvent(item));
-
-                        commands.entity(*door).remove::<DoorDisabled>();
-                    },
-                )
-                .spawn_box(&mut commands);
-
-            di.0 += 1;
-            commands.entity(trigger.target()).insert(Exhausted);
-        }
-        _ => {}
+            )
+            .spawn_box(&mut commands);
+    } else {
+        shadow(step).spawn_box(&mut commands);
+    }
+
+    if step == CRACK_STEP {
+        run_after(
+            Duration::from_secs(1),
+            |mut doors: CrackedDoors| doors.reveal(8392),
+            &mut commands,
+        );
     }
 }
 
+/// Luna's bedroom door, locked until the player finds its key.
 #[derive(Component, Default)]
-#[require(DoorDisabled, Collider::rectangle(16.0, 32.0), Interactable)]
+#[require(Collider::rectangle(16.0, 32.0), Interactable)]
 pub struct LunaDoor;
 
 fn observe_door(
@@ -0,0 +1,399 @@
+use bevy::{
+    input_focus::{InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+use bevy_ldtk_scene::levels::Level;
+use serde::{Deserialize, Serialize};
+
+use super::{LevelId, StartLevels};
+use crate::GameState;
+use crate::flags::StoryFlags;
+use crate::inventory::item::{InventoryItem, pencil, replace_inventory};
+use crate::player::Player;
+use crate::reset::{ResetAppExt, reset_world};
+use crate::save::{data_path, read_data, write_data};
+use crate::widgets::{self, Disabled};
+
+pub struct ChapterPlugin;
+
+impl Plugin for ChapterPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Checkpoints::load())
+            .on_reset(|mut commands: Commands| {
+                commands.remove_resource::<ChapterSpawn>();
+            })
+            .add_systems(
+                OnEnter(GameState::Playing),
+                spawn_player.after(super::load_ldtk),
+            )
+            .add_systems(OnExit(GameState::Menu), close_chapters)
+            .add_observer(record_checkpoint)
+            .add_observer(start_chapter)
+            .add_observer(open_chapters)
+            .add_observer(click_button);
+    }
+}
+
+/// The checkpoint file, in the game's data directory.
+const CHECKPOINTS_FILE: &str = "checkpoints.ron";
+
+/// A point the story can be started from: the beginning of a level.
+#[derive(Debug)]
+pub struct Chapter {
+    pub level: LevelId,
+    pub title: &'static str,
+    /// The story flags set on entering the chapter.
+    pub flags: &'static [(&'static str, i64)],
+}
+
+impl Chapter {
+    pub const ALL: [Self; 3] = [
+        Self {
+            level: LevelId::Level0,
+            title: "Pills",
+            flags: &[],
+        },
+        Self {
+            level: LevelId::Level1,
+            title: "The Visitor",
+            flags: &[(super::pills::PILLS_DISPOSED, 2)],
+        },
+        Self {
+            level: LevelId::Level2,
+            title: "Dark Home",
+            flags: &[
+                (super::pills::PILLS_DISPOSED, 2),
+                (super::tea::TEA_DONE, 1),
+                (super::visitor::VISITOR_KNOCKING, 1),
+                (super::visitor::VISITOR_ANSWERED, 1),
+            ],
+        },
+    ];
+
+    pub fn get(level: LevelId) -> &'static Self {
+        Self::ALL
+            .iter()
+            .find(|chapter| chapter.level == level)
+            .expect("every level has a chapter")
+    }
+
+    /// What the player is carrying on entering the chapter.
+    ///
+    /// Everything picked up in a chapter is used up before it ends, so
+    /// this is the same for each one for now.
+    pub fn inventory(&self) -> Vec<InventoryItem> {
+        vec![pencil()]
+    }
+}
+
+/// The chapters reached on this machine, across every playthrough.
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct Checkpoints {
+    pub unlocked: Vec<LevelId>,
+}
+
+impl Default for Checkpoints {
+    fn default() -> Self {
+        Self {
+            unlocked: vec![LevelId::Level0],
+        }
+    }
+}
+
+impl Checkpoints {
+    fn load() -> Self {
+        let path = data_path(CHECKPOINTS_FILE);
+        if !path.exists() {
+            return Self::default();
+        }
+
+        read_data(&path)
+            .and_then(|data| Ok(ron::from_str(&data)?))
+            .unwrap_or_else(|e| {
+                error!("failed to read checkpoints from {}: {e}", path.display());
+                Self::default()
+            })
+    }
+
+    fn save(&self) -> Result {
+        write_data(&data_path(CHECKPOINTS_FILE), &ron::to_string(self)?)
+    }
+
+    pub fn is_unlocked(&self, level: LevelId) -> bool {
+        self.unlocked.contains(&level)
+    }
+
+    /// Unlock `level`, returning whether it wasn't already.
+    fn reach(&mut self, level: LevelId) -> bool {
+        if self.is_unlocked(level) {
+            return false;
+        }
+
+        self.unlocked.push(level);
+        true
+    }
+}
+
+/// Start the story from the beginning of a chapter, discarding the
+/// current playthrough.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct StartChapter(pub LevelId);
+
+/// Open the chapter select screen over the title screen.
+#[derive(Debug, Default, Event)]
+pub struct OpenChapters;
+
+/// Where to put the player once the chapter's level is loaded.
+#[derive(Resource)]
+struct ChapterSpawn(Vec2);
+
+fn record_checkpoint(
+    trigger: Trigger<OnAdd, Level>,
+    levels: Query<&Level>,
+    mut checkpoints: ResMut<Checkpoints>,
+) -> Result {
+    let Some(level) = LevelId::from_uid(levels.get(trigger.target())?.uid()) else {
+        return Ok(());
+    };
+
+    if !checkpoints.reach(level) {
+        return Ok(());
+    }
+
+    info!("reached chapter {}", Chapter::get(level).title);
+    checkpoints.save()
+}
+
+fn start_chapter(trigger: Trigger<StartChapter>, mut commands: Commands) {
+    let chapter = Chapter::get(trigger.0);
+
+    commands.queue(move |world: &mut World| {
+        info!("starting chapter {}", chapter.title);
+
+        reset_world(world);
+        enter_chapter(world, chapter.level);
+
+        world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Playing);
+    });
+}
+
+/// Set up the starting state for `level`'s chapter, to take effect once
+/// [`GameState::Playing`] is entered.
+///
+/// This expects a fresh world, as at launch or after a reset.
+pub fn enter_chapter(world: &mut World, level: LevelId) {
+    let chapter = Chapter::get(level);
+
+    world.resource_mut::<StartLevels>().0 = vec![chapter.level];
+    world.insert_resource(chapter.flags.iter().copied().collect::<StoryFlags>());
+    replace_inventory(world, chapter.inventory());
+    if let Some(spawn) = chapter.level.spawn_point() {
+        world.insert_resource(ChapterSpawn(spawn));
+    }
+}
+
+/// Levels without a player vessel need the player spawned for them.
+fn spawn_player(
+    spawn: Option<Res<ChapterSpawn>>,
+    players: Query<(), With<Player>>,
+    mut commands: Commands,
+) {
+    let Some(spawn) = spawn else {
+        return;
+    };
+
+    commands.remove_resource::<ChapterSpawn>();
+    if players.is_empty() {
+        commands.spawn((Player, Transform::from_translation(spawn.0.extend(0.))));
+    }
+}
+
+/// The open chapter select screen.
+#[derive(Component)]
+struct ChaptersPanel;
+
+#[derive(Component)]
+enum ChaptersButton {
+    Start(LevelId),
+    Back,
+}
+
+fn open_chapters(
+    _: Trigger<OpenChapters>,
+    existing: Query<(), With<ChaptersPanel>>,
+    checkpoints: Res<Checkpoints>,
+    mut directional_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    mut commands: Commands,
+    server: Res<AssetServer>,
+) {
+    if !existing.is_empty() {
+        return;
+    }
+
+    let font = server.load("fonts/raster-forge.ttf");
+    let panel = commands
+        .spawn((
+            ChaptersPanel,
+            widgets::overlay(&input_focus, 10, Color::BLACK.with_alpha(0.9), 15.0),
+            children![(
+                Text::new("Chapters"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 40.0,
+                    ..Default::default()
+                },
+                Node {
+                    margin: UiRect::bottom(Val::Px(20.0)),
+                    ..default()
+                },
+            )],
+        ))
+        .id();
+
+    let mut buttons = Vec::new();
+    for (i, chapter) in Chapter::ALL.iter().enumerate() {
+        let label = if checkpoints.is_unlocked(chapter.level) {
+            format!("{}. {}", i + 1, chapter.title)
+        } else {
+            format!("{}. ???", i + 1)
+        };
+
+        let mut button = commands.spawn((
+            button(label, ChaptersButton::Start(chapter.level), &font),
+            ChildOf(panel),
+        ));
+
+        if checkpoints.is_unlocked(chapter.level) {
+            buttons.push(button.id());
+        } else {
+            button.insert(Disabled);
+        }
+    }
+
+    let back = commands
+        .spawn((
+            button("Back".into(), ChaptersButton::Back, &font),
+            ChildOf(panel),
+        ))
+        .id();
+    buttons.push(back);
+
+    directional_nav_map.add_looping_edges(&buttons, CompassOctant::South);
+    input_focus.set(buttons[0]);
+}
+
+fn button(text: String, action: ChaptersButton, font: &Handle<Font>) -> impl Bundle {
+    (
+        action,
+        widgets::button(
+            text,
+            32.0,
+            Node {
+                width: Val::Px(360.0),
+                height: Val::Px(50.0),
+                ..default()
+            },
+            font,
+        ),
+    )
+}
+
+fn click_button(
+    trigger: Trigger<Pointer<Click>>,
+    buttons: Query<&ChaptersButton, Without<Disabled>>,
+    mut commands: Commands,
+) {
+    let Ok(button) = buttons.get(trigger.target()) else {
+        return;
+    };
+
+    match button {
+        ChaptersButton::Start(level) => commands.trigger(StartChapter(*level)),
+        ChaptersButton::Back => commands.run_system_cached(close_chapters),
+    }
+}
+
+fn close_chapters(panel: Option<Single<Entity, With<ChaptersPanel>>>, mut commands: Commands) {
+    if let Some(panel) = panel {
+        commands.run_system_cached_with(widgets::close_overlay, *panel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::levels::pills::PILLS_DISPOSED;
+    use crate::levels::tea::TEA_DONE;
+    use crate::testing::TestApp;
+
+    fn chapter_app() -> TestApp {
+        let mut app = TestApp::new(ChapterPlugin);
+        app.init_resource::<StartLevels>()
+            .insert_resource(Checkpoints::default());
+        app
+    }
+
+    #[test]
+    fn checkpoints_unlock_each_level_once() {
+        let mut checkpoints = Checkpoints::default();
+        assert!(checkpoints.is_unlocked(LevelId::Level0));
+        assert!(!checkpoints.is_unlocked(LevelId::Level1));
+
+        assert!(checkpoints.reach(LevelId::Level1));
+        assert!(!checkpoints.reach(LevelId::Level1));
+        assert!(!checkpoints.reach(LevelId::Level0));
+        assert_eq!(checkpoints.unlocked, [LevelId::Level0, LevelId::Level1]);
+    }
+
+    #[test]
+    fn every_chapter_after_the_first_has_a_spawn_point() {
+        for chapter in &Chapter::ALL[1..] {
+            let spawn = chapter.level.spawn_point().expect("chapter has a spawn");
+            assert_eq!(LevelId::containing(spawn), Some(chapter.level));
+        }
+    }
+
+    #[test]
+    fn entering_a_chapter_sets_up_its_story() {
+        let mut app = chapter_app();
+        enter_chapter(app.world_mut(), LevelId::Level2);
+
+        assert_eq!(app.world().resource::<StartLevels>().0, [LevelId::Level2]);
+        assert_eq!(app.flags().get(PILLS_DISPOSED), 2);
+        assert_eq!(app.flags().get(TEA_DONE), 1);
+        assert!(app.has_item("Pencil"));
+
+        app.world_mut().run_system_cached(spawn_player).unwrap();
+        app.update();
+
+        let position = app
+            .world_mut()
+            .query_filtered::<&Transform, With<Player>>()
+            .single(app.world())
+            .unwrap()
+            .translation
+            .xy();
+        assert_eq!(Some(position), LevelId::Level2.spawn_point());
+        assert!(!app.world().contains_resource::<ChapterSpawn>());
+    }
+
+    #[test]
+    fn starting_a_chapter_discards_the_playthrough() {
+        let mut app = chapter_app();
+        app.world_mut()
+            .resource_mut::<StoryFlags>()
+            .set_value("unrelated", 3);
+
+        app.world_mut().trigger(StartChapter(LevelId::Level1));
+        app.update();
+
+        assert_eq!(app.world().resource::<StartLevels>().0, [LevelId::Level1]);
+        assert_eq!(app.flags().get("unrelated"), 0);
+        assert_eq!(app.flags().get(PILLS_DISPOSED), 2);
+        assert_eq!(app.flags().get(TEA_DONE), 0);
+    }
+}
@@ -0,0 +1,513 @@
+use std::time::Duration;
+
+use avian2d::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy_enhanced_input::events::Fired;
+use bevy_ldtk_scene::levels::{Level, LevelLoader};
+use bevy_seedling::prelude::*;
+
+use super::{LevelId, ScreenTransition};
+use crate::interactions::{InteractAction, Interactable};
+use crate::inventory::item::{Inventory, InventoryItem};
+use crate::inventory::item_use::{HeldItem, ItemUsedOn};
+use crate::player::{InhibitAddEvent, InhibitRemoveEvent, Player, PlayerCollider};
+use crate::save::SaveEvent;
+use crate::sequence::run_after;
+use crate::textbox::{TextBlurb, TextboxEvent, TextboxPriority};
+use crate::{Layer, TILE_SIZE, world};
+
+pub struct DoorPlugin;
+
+impl Plugin for DoorPlugin {
+    fn build(&self, app: &mut App) {
+        register_ldtk_doors(app);
+
+        app.add_systems(Update, door_sprites)
+            .add_observer(interact)
+            .add_observer(use_key)
+            .add_observer(mark_unlocked)
+            .add_observer(announce_unlocked);
+    }
+}
+
+/// A door the player can walk through.
+///
+/// Doors are built from their LDtk entities with [`LdtkDoor`], so the
+/// same interaction, locking, and transition logic covers every door type.
+/// Any LDtk entity with a `Target` field is a door; see `build.rs`.
+#[derive(Debug, Clone, Component)]
+pub struct Door {
+    pub destination: Option<DoorDestination>,
+    /// The level to spawn when walking through.
+    ///
+    /// If this is `None` and the destination lies outside the door's
+    /// level, the level containing it is spawned instead.
+    pub load: Option<LevelId>,
+    pub lock: DoorLock,
+}
+
+impl Door {
+    /// A door leading to `destination`, or a locked door if there is none.
+    pub fn to(destination: Option<DoorDestination>) -> Self {
+        Self {
+            lock: if destination.is_some() {
+                DoorLock::Unlocked
+            } else {
+                DoorLock::locked()
+            },
+            destination,
+            load: None,
+        }
+    }
+
+    /// Spawn the level named by an LDtk `load` field when walking through.
+    pub fn load(mut self, load: &str) -> Self {
+        if load.is_empty() {
+            return self;
+        }
+
+        self.load = LevelId::from_name(load);
+        if self.load.is_none() {
+            error!("door loads unknown level `{load}`");
+        }
+
+        self
+    }
+
+    /// Lock this door until the player brings the item named `key`.
+    pub fn key(mut self, key: &str) -> Self {
+        if key.is_empty() {
+            return self;
+        }
+
+        let message = match self.lock {
+            DoorLock::Locked { message, .. } => message,
+            DoorLock::Unlocked => None,
+        };
+        self.lock = DoorLock::Locked {
+            key: Some(key.into()),
+            message,
+        };
+
+        self
+    }
+
+    /// Show `message` when the player tries this door while it's locked.
+    pub fn locked_message(mut self, message: impl Into<String>) -> Self {
+        let message = message.into();
+        if message.is_empty() {
+            return self;
+        }
+
+        if let DoorLock::Locked { message: m, .. } = &mut self.lock {
+            *m = Some(message);
+        }
+
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum DoorDestination {
+    /// A tile relative to the door's level.
+    Tile(Vec2),
+    /// A position in world space.
+    Position(Vec2),
+}
+
+#[derive(Debug, Default, Clone)]
+pub enum DoorLock {
+    #[default]
+    Unlocked,
+    Locked {
+        /// The name of an inventory item that unlocks the door.
+        key: Option<String>,
+        message: Option<String>,
+    },
+}
+
+impl DoorLock {
+    pub fn locked() -> Self {
+        Self::Locked {
+            key: None,
+            message: None,
+        }
+    }
+}
+
+/// Disables a door entirely: it can't be opened and doesn't react when tried.
+///
+/// Hidden doors, like the cracked side doors, start out disabled.
+#[derive(Component, Default)]
+pub struct DoorDisabled;
+
+/// Triggered on a door when the player walks through it.
+#[derive(Debug, Event)]
+pub struct DoorOpened;
+
+/// Triggered on a door when the player tries it while it's locked.
+#[derive(Debug, Event)]
+pub struct DoorLocked;
+
+/// Triggered on a door the first time it's unlocked or enabled.
+#[derive(Debug, Event)]
+pub struct DoorUnlocked;
+
+/// Marks a door that started out locked or disabled and has since been unlocked.
+#[derive(Component)]
+pub(super) struct UnlockedDoor;
+
+/// An LDtk entity that should behave as a [`Door`].
+pub trait LdtkDoor: Component {
+    /// The collider and interaction setup for this door.
+    type Shape: Component + Default;
+
+    fn door(&self) -> Door;
+}
+
+pub trait DoorAppExt {
+    fn register_door<T: LdtkDoor>(&mut self) -> &mut Self;
+}
+
+impl DoorAppExt for App {
+    fn register_door<T: LdtkDoor>(&mut self) -> &mut Self {
+        self.register_required_components::<T, T::Shape>()
+            .add_observer(
+                |trigger: Trigger<OnAdd, T>, doors: Query<&T>, mut commands: Commands| {
+                    if let Ok(door) = doors.get(trigger.target()) {
+                        commands.entity(trigger.target()).insert(door.door());
+                    }
+                },
+            )
+    }
+}
+
+#[derive(Default, Component)]
+#[require(
+    Collider::rectangle(16., 24.),
+    Sensor,
+    CollidingEntities,
+    CollisionLayers::new(Layer::Default, Layer::Player)
+)]
+pub struct VerticalDoor;
+
+#[derive(Default, Component)]
+#[require(
+    Collider::rectangle(24., 24.),
+    Sensor,
+    CollidingEntities,
+    CollisionLayers::new(Layer::Default, Layer::Player),
+    Interactable
+)]
+pub struct SideDoor;
+
+// `LdtkDoor` impls and `register_ldtk_doors` for every door in the LDtk project.
+include!(concat!(env!("OUT_DIR"), "/doors.rs"));
+
+/// Open and closed frames for a door, laid out side by side in `image`.
+#[derive(Component)]
+#[require(DoorState)]
+pub struct DoorSprites {
+    pub image: &'static str,
+    pub size: Vec2,
+}
+
+impl DoorSprites {
+    pub fn new(image: &'static str, size: Vec2) -> Self {
+        Self { image, size }
+    }
+}
+
+#[derive(Default, Component)]
+pub enum DoorState {
+    #[default]
+    Closed,
+    Open,
+}
+
+fn door_sprites(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    doors: Query<(Entity, &DoorState, &DoorSprites), Changed<DoorState>>,
+) {
+    for (entity, state, sprites) in doors.iter() {
+        let offset = match state {
+            DoorState::Closed => 0.,
+            DoorState::Open => sprites.size.x,
+        };
+
+        commands.entity(entity).insert(Sprite {
+            image: server.load(sprites.image),
+            rect: Some(Rect::from_corners(
+                Vec2::new(offset, 0.),
+                Vec2::new(offset + sprites.size.x, sprites.size.y),
+            )),
+            ..Default::default()
+        });
+    }
+}
+
+fn interact(
+    _: Trigger<Fired<InteractAction>>,
+    doors: Query<(Entity, &Door, &CollidingEntities, &ChildOf), Without<DoorDisabled>>,
+    player_collider: Single<Entity, With<PlayerCollider>>,
+    inventory: Query<&Children, With<Inventory>>,
+    items: Query<&InventoryItem>,
+    held: Res<HeldItem>,
+    mut writer: EventWriter<TextboxEvent>,
+    mut commands: Commands,
+    server: Res<AssetServer>,
+) {
+    // Items are used on the door instead.
+    if held.0.is_some() {
+        return;
+    }
+
+    for (entity, door, _, child_of) in doors
+        .iter()
+        .filter(|(_, _, colliding, _)| colliding.contains(&*player_collider))
+    {
+        if let DoorLock::Locked { key, message } = &door.lock {
+            let has_key = key.as_ref().is_some_and(|key| {
+                inventory
+                    .iter()
+                    .flat_map(|children| children.iter())
+                    .filter_map(|item| items.get(item).ok())
+                    .any(|item| item.name == *key)
+            });
+
+            if !has_key {
+                commands.spawn(SamplePlayer {
+                    sample: server.load("audio/sfx/door-handle.wav"),
+                    volume: Volume::Linear(0.5),
+                    ..Default::default()
+                });
+                if let Some(message) = message {
+                    writer.write(
+                        TextboxEvent::section(TextBlurb::narrator(message.clone()))
+                            .with_priority(TextboxPriority::Flavor),
+                    );
+                }
+                commands.entity(entity).trigger(DoorLocked);
+                continue;
+            }
+
+            commands.queue(unlock_door(entity));
+        }
+
+        let Some(destination) = door.destination.clone() else {
+            continue;
+        };
+
+        let level = child_of.parent();
+        let load = door.load;
+        commands.queue(move |world: &mut World| -> Result {
+            world.run_system_cached_with(open_door, (entity, level, destination, load))?
+        });
+    }
+}
+
+/// Unlock a door by using its key on it from the inventory.
+fn use_key(
+    mut trigger: Trigger<ItemUsedOn>,
+    doors: Query<&Door>,
+    items: Query<&InventoryItem>,
+    mut commands: Commands,
+    server: Res<AssetServer>,
+) {
+    let door = trigger.target();
+    let Ok(Door {
+        lock: DoorLock::Locked { key: Some(key), .. },
+        ..
+    }) = doors.get(door)
+    else {
+        return;
+    };
+
+    if !items.get(trigger.item).is_ok_and(|item| item.name == *key) {
+        trigger.event_mut().reject("That won't open it.");
+        return;
+    }
+
+    commands.queue(unlock_door(door));
+    commands.entity(door).with_child((
+        PlaybackSettings {
+            on_complete: OnComplete::Remove,
+            ..Default::default()
+        },
+        SamplePlayer::new(server.load("audio/sfx/door-open.wav")),
+        crate::audio::SpatialPool,
+    ));
+    trigger.event_mut().consume();
+}
+
+fn open_door(
+    In((door, level, destination, load)): In<(Entity, Entity, DoorDestination, Option<LevelId>)>,
+    player: Single<Entity, With<Player>>,
+    transforms: Query<&GlobalTransform>,
+    levels: Query<&Level>,
+    mut states: Query<&mut DoorState>,
+    mut loader: Single<&mut LevelLoader>,
+    mut commands: Commands,
+    server: Res<AssetServer>,
+) -> Result {
+    let level_t = transforms.get(level)?.translation().xy();
+    let position = match destination {
+        DoorDestination::Tile(tile) => level_t + Vec2::new(tile.x * TILE_SIZE, -tile.y * TILE_SIZE),
+        DoorDestination::Position(position) => position,
+    };
+
+    let current = LevelId::from_uid(levels.get(level)?.uid());
+    let load = load.or_else(|| LevelId::containing(position).filter(|next| Some(*next) != current));
+
+    if let Some(next) = load {
+        if !levels.iter().any(|level| level.uid() == next.uid()) {
+            next.spawn(&mut loader);
+        }
+
+        if let Some(current) = current.filter(|current| *current != next) {
+            run_after(
+                Duration::from_secs(2),
+                move |mut loader: Single<&mut LevelLoader>| {
+                    current.despawn(&mut loader);
+                },
+                &mut commands,
+            );
+        }
+    }
+
+    if let Ok(mut state) = states.get_mut(door) {
+        *state = DoorState::Open;
+    }
+
+    commands.entity(door).trigger(DoorOpened);
+    commands.entity(*player).trigger(InhibitAddEvent);
+    commands.spawn(
+        SamplePlayer::new(server.load("audio/sfx/door.wav")).with_volume(Volume::Decibels(-12.0)),
+    );
+
+    commands.spawn(ScreenTransition::new(
+        Duration::from_millis(250),
+        move |mut player: Single<&mut Transform, With<Player>>| {
+            player.translation.x = position.x;
+            player.translation.y = position.y;
+        },
+        move |player: Single<Entity, With<Player>>,
+              mut states: Query<&mut DoorState>,
+              mut commands: Commands,
+              mut saves: EventWriter<SaveEvent>| {
+            if let Ok(mut state) = states.get_mut(door) {
+                *state = DoorState::Closed;
+            }
+
+            commands.entity(*player).trigger(InhibitRemoveEvent);
+            saves.write_default();
+        },
+    ));
+
+    Ok(())
+}
+
+/// Unlock a door's [`DoorLock`] and remove [`DoorDisabled`].
+pub fn unlock_door(door: Entity) -> impl FnOnce(&mut World) + Send + 'static {
+    move |world: &mut World| {
+        let Ok(mut entity) = world.get_entity_mut(door) else {
+            return;
+        };
+
+        // Enabled doors are marked by `mark_unlocked`.
+        entity.remove::<DoorDisabled>();
+
+        let Some(mut lock) = entity.get_mut::<Door>() else {
+            return;
+        };
+
+        if matches!(lock.lock, DoorLock::Unlocked) {
+            return;
+        }
+
+        lock.lock = DoorLock::Unlocked;
+        entity.insert(UnlockedDoor);
+    }
+}
+
+fn mark_unlocked(trigger: Trigger<OnRemove, DoorDisabled>, mut commands: Commands) {
+    let door = trigger.target();
+
+    commands.queue(move |world: &mut World| {
+        // This also fires when the door is despawned, in which case there's nothing to mark.
+        if let Ok(mut entity) = world.get_entity_mut(door) {
+            entity.insert(UnlockedDoor);
+        }
+    });
+}
+
+/// [`UnlockedDoor`] is only added once, so unlocking and enabling the
+/// same door announces it a single time.
+fn announce_unlocked(trigger: Trigger<OnAdd, UnlockedDoor>, mut commands: Commands) {
+    commands.entity(trigger.target()).trigger(DoorUnlocked);
+}
+
+/// Reveals the hidden cracked side doors.
+#[derive(SystemParam)]
+pub struct CrackedDoors<'w, 's> {
+    commands: Commands<'w, 's>,
+    server: Res<'w, AssetServer>,
+    cracked: Query<'w, 's, (Entity, &'static world::CrackedSideDoor1)>,
+    side: Query<'w, 's, (Entity, &'static world::SideDoor1)>,
+}
+
+impl CrackedDoors<'_, '_> {
+    /// Open the cracked door with the LDtk `id`, replacing the side door it shares an id with.
+    pub fn reveal(&mut self, id: i64) {
+        for (entity, _) in self.side.iter().filter(|(_, door)| door.id as i64 == id) {
+            self.commands.entity(entity).despawn();
+        }
+
+        for (entity, _) in self.cracked.iter().filter(|(_, door)| door.id as i64 == id) {
+            self.commands
+                .entity(entity)
+                .remove::<DoorDisabled>()
+                .insert((
+                    Visibility::Visible,
+                    // spatializing sound on door
+                    PlaybackSettings {
+                        on_complete: OnComplete::Remove,
+                        ..Default::default()
+                    },
+                    SamplePlayer::new(self.server.load("audio/sfx/door-open.wav")),
+                    crate::audio::SpatialPool,
+                ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock(door: Door) -> (Option<String>, Option<String>) {
+        match door.lock {
+            DoorLock::Locked { key, message } => (key, message),
+            DoorLock::Unlocked => panic!("door should be locked"),
+        }
+    }
+
+    #[test]
+    fn doors_without_destinations_are_locked() {
+        let door = Door::to(None).key("").locked_message("");
+        assert_eq!(lock(door), (None, None));
+
+        let door = Door::to(Some(DoorDestination::Tile(Vec2::ONE))).key("");
+        assert!(matches!(door.lock, DoorLock::Unlocked));
+    }
+
+    #[test]
+    fn key_locks_the_door() {
+        let door = Door::to(Some(DoorDestination::Tile(Vec2::ONE)))
+            .key("Key")
+            .locked_message("Locked...");
+        assert_eq!(lock(door), (Some("Key".into()), Some("Locked...".into())));
+    }
+}
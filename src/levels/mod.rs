@@ -3,28 +3,28 @@ use std::time::Duration;
 use avian2d::prelude::*;
 use bevy::ecs::component::HookContext;
 use bevy::ecs::world::DeferredWorld;
-use bevy::platform::collections::HashMap;
+use bevy::platform::collections::{HashMap, HashSet};
 use bevy::prelude::*;
-use bevy_enhanced_input::events::Fired;
 use bevy_ldtk_scene::levels::{Level, LevelLoader};
+use bevy_ldtk_scene::prelude::LevelMetaExt;
 use bevy_ldtk_scene::world::LevelUid;
 use bevy_optix::camera::MainCamera;
 use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
-use bevy_seedling::prelude::Volume;
-use bevy_seedling::sample::SamplePlayer;
-use bevy_sequence::combinators::delay::run_after;
 use bevy_tween::combinator::{sequence, tween};
 use bevy_tween::interpolate::sprite_color_to;
 use bevy_tween::prelude::{AnimationBuilderExt, EaseKind};
 use bevy_tween::tween::IntoTarget;
+use serde::{Deserialize, Serialize};
 
 use crate::callback::Callback;
-use crate::interactions::{InteractAction, Interactable};
-use crate::player::{InhibitAddEvent, InhibitRemoveEvent, Player, PlayerCollider};
-use crate::textbox::{TextBlurb, TextboxEvent};
+use crate::player::Player;
+use crate::reset::ResetAppExt;
+use crate::save::{Persistent, SaveAppExt};
 use crate::{GameState, HexColor, Layer, TILE_SIZE, world};
 
 mod bathroom;
+pub mod chapter;
+pub mod door;
 mod pills;
 mod tea;
 mod visitor;
@@ -38,20 +38,133 @@ impl Plugin for LevelPlugin {
             visitor::VisitorPlugin,
             bathroom::BathroomPlugin,
             tea::TeaPlugin,
+            door::DoorPlugin,
+            chapter::ChapterPlugin,
         ))
         .register_required_components::<world::Teleport, Teleporter>()
-        .register_required_components::<world::LunaDoor, VerticalDoor>()
-        .register_required_components::<world::HallDoor1, VerticalDoor>()
-        .register_required_components::<world::FrontDoor, VerticalDoor>()
-        .register_required_components::<world::SideDoor1, Door>()
-        .register_required_components::<world::SideDoor2, Door>()
-        .register_required_components::<world::BathroomDoor, VerticalDoor>()
-        .register_required_components::<world::BathroomExitDoor, VerticalDoor>()
-        .register_required_components::<world::CrackedSideDoor1, Door>()
+        .init_resource::<StartLevels>()
+        .init_resource::<PendingDoors>()
+        .register_persistent::<LevelProgress>()
+        .despawn_on_reset::<Level>()
+        .despawn_on_reset::<bevy_ldtk_scene::World>()
+        .despawn_on_reset::<ScreenTransition>()
+        .reset_resource::<StartLevels>()
+        .reset_resource::<PendingDoors>()
         .add_systems(Update, (add_tile_collision, manage_transitions))
+        .add_systems(PostUpdate, restore_doors)
         .add_systems(OnEnter(GameState::Playing), load_ldtk)
-        .add_observer(teleport)
-        .add_observer(door);
+        .add_observer(teleport);
+    }
+}
+
+/// The LDtk levels that make up the story.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LevelId {
+    Level0,
+    Level1,
+    Level2,
+}
+
+impl LevelId {
+    pub const ALL: [Self; 3] = [Self::Level0, Self::Level1, Self::Level2];
+
+    pub fn uid(self) -> LevelUid {
+        match self {
+            Self::Level0 => world::Level0.uid(),
+            Self::Level1 => world::Level1.uid(),
+            Self::Level2 => world::Level2.uid(),
+        }
+    }
+
+    pub fn from_uid(uid: LevelUid) -> Option<Self> {
+        Self::ALL.into_iter().find(|level| level.uid() == uid)
+    }
+
+    /// Parse the level names used in LDtk fields, like `"level1"`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "level0" => Some(Self::Level0),
+            "level1" => Some(Self::Level1),
+            "level2" => Some(Self::Level2),
+            _ => None,
+        }
+    }
+
+    /// The level's identifier in the LDtk project.
+    pub fn identifier(self) -> &'static str {
+        match self {
+            Self::Level0 => "Level_0",
+            Self::Level1 => "Level_1",
+            Self::Level2 => "Level_2",
+        }
+    }
+
+    /// The level's bounds in world space.
+    ///
+    /// These are read from the LDtk project at build time, so they're
+    /// available before the level is spawned.
+    pub fn rect(self) -> Rect {
+        LEVEL_BOUNDS
+            .iter()
+            .find(|(identifier, _)| *identifier == self.identifier())
+            .map(|(_, rect)| *rect)
+            .unwrap_or_else(|| panic!("`{}` is missing from the LDtk project", self.identifier()))
+    }
+
+    /// Where the player starts when jumping straight to this level, from
+    /// the level's `spawn` field in LDtk.
+    ///
+    /// `None` if the level places the player itself.
+    pub fn spawn_point(self) -> Option<Vec2> {
+        LEVEL_SPAWNS
+            .iter()
+            .find(|(identifier, _)| *identifier == self.identifier())
+            .map(|(_, spawn)| *spawn)
+    }
+
+    /// The level containing a world space position.
+    pub fn containing(position: Vec2) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.rect().contains(position))
+    }
+
+    /// A new loader with only this level spawned.
+    pub fn loader(self) -> LevelLoader {
+        match self {
+            Self::Level0 => LevelLoader::levels(world::Level0),
+            Self::Level1 => LevelLoader::levels(world::Level1),
+            Self::Level2 => LevelLoader::levels(world::Level2),
+        }
+    }
+
+    pub fn spawn(self, loader: &mut LevelLoader) {
+        match self {
+            Self::Level0 => loader.spawn(world::Level0),
+            Self::Level1 => loader.spawn(world::Level1),
+            Self::Level2 => loader.spawn(world::Level2),
+        }
+    }
+
+    pub fn despawn(self, loader: &mut LevelLoader) {
+        match self {
+            Self::Level0 => loader.despawn(world::Level0),
+            Self::Level1 => loader.despawn(world::Level1),
+            Self::Level2 => loader.despawn(world::Level2),
+        }
+    }
+}
+
+// `LEVEL_BOUNDS` and `LEVEL_SPAWNS` for the levels in the LDtk project.
+include!(concat!(env!("OUT_DIR"), "/levels.rs"));
+
+/// The levels spawned when entering [`GameState::Playing`].
+#[derive(Resource)]
+pub struct StartLevels(pub Vec<LevelId>);
+
+impl Default for StartLevels {
+    fn default() -> Self {
+        Self(vec![LevelId::Level0])
     }
 }
 
@@ -204,222 +317,132 @@ fn manage_transitions(
     }
 }
 
-#[derive(Default, Component)]
-#[require(
-    Collider::rectangle(16., 24.),
-    Sensor,
-    CollidingEntities,
-    CollisionLayers::new(Layer::Default, Layer::Player)
-)]
-struct VerticalDoor;
+fn load_ldtk(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    mut camera: Single<&mut Camera, With<MainCamera>>,
+    start: Res<StartLevels>,
+) {
+    camera.clear_color = ClearColorConfig::Custom(HexColor(0x252525).into());
 
-#[derive(Default, Component)]
-#[require(
-    Collider::rectangle(24., 24.),
-    Sensor,
-    CollidingEntities,
-    CollisionLayers::new(Layer::Default, Layer::Player),
-    Interactable
-)]
-struct Door;
-
-#[derive(Component, Default)]
-pub struct DoorDisabled;
-
-fn door(
-    _: Trigger<Fired<InteractAction>>,
-
-    luna_door: Query<(&world::LunaDoor, &CollidingEntities, &ChildOf), Without<DoorDisabled>>,
-    hall_doors1: Query<(&world::HallDoor1, &CollidingEntities, &ChildOf), Without<DoorDisabled>>,
-    hall_doors2: Query<(&world::FrontDoor, &CollidingEntities, &ChildOf), Without<DoorDisabled>>,
-    side_doors1: Query<(&world::SideDoor1, &CollidingEntities, &ChildOf), Without<DoorDisabled>>,
-    side_doors2: Query<(&world::SideDoor2, &CollidingEntities, &ChildOf), Without<DoorDisabled>>,
-    bathroom_door1: Query<
-        (&world::BathroomDoor, &CollidingEntities, &ChildOf),
-        Without<DoorDisabled>,
-    >,
-    bathroom_door2: Query<(
-        &world::BathroomExitDoor,
-        &CollidingEntities,
-        &ChildOf,
-        Option<&DoorDisabled>,
-    )>,
-    cracked_side_door1: Query<
-        (&world::CrackedSideDoor1, &CollidingEntities, &ChildOf),
-        Without<DoorDisabled>,
-    >,
+    let mut levels = start.0.iter().copied();
+    let mut loader = levels.next().unwrap_or(LevelId::Level0).loader();
+    for level in levels {
+        level.spawn(&mut loader);
+    }
+
+    commands.spawn((
+        bevy_ldtk_scene::HotWorld(server.load("ldtk/time-marches-on.ldtk")),
+        bevy_ldtk_scene::World(server.load("ldtk/time-marches-on.ron")),
+        loader,
+    ));
+}
 
-    player: Single<(Entity, &mut Transform), With<Player>>,
-    player_collider: Single<Entity, With<PlayerCollider>>,
-    transforms: Query<&GlobalTransform>,
-    mut writer: EventWriter<TextboxEvent>,
+/// Identifies a door by its level and position within the level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+struct DoorKey {
+    level: LevelId,
+    x: i32,
+    y: i32,
+}
 
-    mut commands: Commands,
-    server: Res<AssetServer>,
+impl DoorKey {
+    fn new(transform: &Transform, child_of: &ChildOf, levels: &Query<&Level>) -> Option<Self> {
+        let level = LevelId::from_uid(levels.get(child_of.parent()).ok()?.uid())?;
 
-    mut loader: Single<&mut LevelLoader>,
-) -> Result {
-    for (target, child_of, luna, load) in bathroom_door1
-        .iter()
-        .map(|(door, colliding, child_of)| (door.target, colliding, child_of, false, ""))
-        .chain(
-            bathroom_door2
-                .iter()
-                .map(|(door, colliding, child_of, disabled)| {
-                    (
-                        disabled.is_none().then_some(door.target).or(None).flatten(),
-                        colliding,
-                        child_of,
-                        false,
-                        "",
-                    )
-                }),
-        )
-        .chain(
-            luna_door
-                .iter()
-                .map(|(door, colliding, child_of)| (door.target, colliding, child_of, true, "")),
-        )
-        .chain(side_doors1.iter().map(|(door, colliding, child_of)| {
-            if door.id == 2001 {
-                (
-                    Some(Vec2::new(12.0, 111.0)),
-                    colliding,
-                    child_of,
-                    false,
-                    "level1",
-                )
-            } else {
-                (door.target, colliding, child_of, false, "")
-            }
-        }))
-        .chain(
-            side_doors2
-                .iter()
-                .map(|(door, colliding, child_of)| (door.target, colliding, child_of, false, "")),
-        )
-        .chain(hall_doors1.iter().map(|(door, colliding, child_of)| {
-            let level_t = transforms.get(child_of.parent()).unwrap().translation();
-
-            (
-                door.target.or_else(|| {
-                    (door.x != 0.0 && door.y != 0.0).then_some(Vec2::new(
-                        (door.x - level_t.x) / 16.,
-                        -(-door.y - level_t.y) / 16.,
-                    ))
-                }),
-                colliding,
-                child_of,
-                false,
-                door.load.as_str(),
-            )
-        }))
-        .chain(
-            hall_doors2
-                .iter()
-                .map(|(door, colliding, child_of)| (door.target, colliding, child_of, false, "")),
-        )
-        .chain(
-            cracked_side_door1
-                .iter()
-                .map(|(door, colliding, child_of)| {
-                    let level_t = transforms.get(child_of.parent()).unwrap().translation();
-                    (
-                        door.target.or_else(|| {
-                            (door.x != 0.0 && door.y != 0.0).then_some(Vec2::new(
-                                (door.x - level_t.x) / 16.,
-                                -(-door.y - level_t.y) / 16.,
-                            ))
-                        }),
-                        colliding,
-                        child_of,
-                        false,
-                        door.load.as_str(),
-                    )
-                }),
-        )
-        .filter_map(|(target, colliding, child_of, luna, load)| {
-            colliding
-                .contains(&*player_collider)
-                .then_some((target, child_of, luna, load))
+        Some(Self {
+            level,
+            x: transform.translation.x.round() as i32,
+            y: transform.translation.y.round() as i32,
         })
-    {
-        info!("{target:?}, {child_of:?}, {luna:?}, {load}");
-
-        if !load.is_empty() {
-            match load {
-                "level1" => {
-                    loader.spawn(world::Level1);
-                    run_after(
-                        Duration::from_secs(2),
-                        |mut loader: Single<&mut LevelLoader>| {
-                            loader.despawn(world::Level0);
-                        },
-                        &mut commands,
-                    );
-                }
-                "level2" => {
-                    loader.spawn(world::Level2);
-                    run_after(
-                        Duration::from_secs(2),
-                        |mut loader: Single<&mut LevelLoader>| {
-                            loader.despawn(world::Level1);
-                        },
-                        &mut commands,
-                    );
-                }
-                _ => panic!("{}", load),
-            }
-        }
+    }
+}
 
-        match target {
-            Some(target) => {
-                let level_t = transforms.get(child_of.parent())?.translation();
-
-                commands.entity(player.0).trigger(InhibitAddEvent);
-                commands.spawn(
-                    SamplePlayer::new(server.load("audio/sfx/door.wav"))
-                        .with_volume(Volume::Decibels(-12.0)),
-                );
-
-                commands.spawn(ScreenTransition::new(
-                    Duration::from_millis(250),
-                    move |mut player: Single<&mut Transform, With<Player>>| {
-                        player.translation.x = target.x * 16. + level_t.x;
-                        player.translation.y = -target.y * 16. + level_t.y;
-                    },
-                    |player: Single<Entity, With<Player>>, mut commands: Commands| {
-                        commands.entity(*player).trigger(InhibitRemoveEvent);
-                    },
-                ));
-            }
-            None => {
-                commands.spawn(SamplePlayer {
-                    sample: server.load("audio/sfx/door-handle.wav"),
-                    volume: Volume::Linear(0.5),
-                    ..Default::default()
-                });
-                if luna {
-                    writer.write(TextboxEvent::section(TextBlurb::narrator("Locked...")));
-                }
-            }
+/// Doors from a restored save that should be enabled once their level spawns.
+#[derive(Default, Resource)]
+struct PendingDoors(HashSet<DoorKey>);
+
+#[derive(Serialize, Deserialize)]
+struct LevelProgress {
+    levels: Vec<LevelId>,
+    unlocked_doors: Vec<DoorKey>,
+}
+
+impl Persistent for LevelProgress {
+    const KEY: &'static str = "levels";
+
+    fn capture(world: &mut World) -> Option<Self> {
+        let levels = world
+            .query::<&Level>()
+            .iter(world)
+            .filter_map(|level| LevelId::from_uid(level.uid()))
+            .collect::<Vec<_>>();
+
+        if levels.is_empty() {
+            return None;
         }
+
+        let mut doors = world.query_filtered::<(&Transform, &ChildOf), With<door::UnlockedDoor>>();
+        let mut level_query = world.query::<&Level>();
+        let level_query = level_query.query(world);
+        let unlocked_doors = doors
+            .iter(world)
+            .filter_map(|(transform, child_of)| DoorKey::new(transform, child_of, &level_query))
+            .collect();
+
+        Some(Self {
+            levels,
+            unlocked_doors,
+        })
     }
 
-    Ok(())
+    fn restore(self, world: &mut World) {
+        world.resource_mut::<StartLevels>().0 = self.levels;
+        world.resource_mut::<PendingDoors>().0 = self.unlocked_doors.into_iter().collect();
+    }
 }
 
-fn load_ldtk(
+fn restore_doors(
+    mut pending: ResMut<PendingDoors>,
+    doors: Query<
+        (
+            Entity,
+            &Transform,
+            &ChildOf,
+            Option<&world::CrackedSideDoor1>,
+        ),
+        With<door::Door>,
+    >,
+    side_doors: Query<(Entity, &world::SideDoor1, &ChildOf)>,
+    levels: Query<&Level>,
     mut commands: Commands,
-    server: Res<AssetServer>,
-    mut camera: Single<&mut Camera, With<MainCamera>>,
 ) {
-    camera.clear_color = ClearColorConfig::Custom(HexColor(0x252525).into());
-    commands.spawn((
-        bevy_ldtk_scene::HotWorld(server.load("ldtk/time-marches-on.ldtk")),
-        bevy_ldtk_scene::World(server.load("ldtk/time-marches-on.ron")),
-        bevy_ldtk_scene::prelude::LevelLoader::levels(world::Level1),
-    ));
+    if pending.0.is_empty() {
+        return;
+    }
+
+    for (entity, transform, child_of, cracked) in doors.iter() {
+        let Some(key) = DoorKey::new(transform, child_of, &levels) else {
+            continue;
+        };
+
+        if !pending.0.remove(&key) {
+            continue;
+        }
+
+        commands.queue(door::unlock_door(entity));
+
+        // Cracked doors replace their side door once opened.
+        if let Some(cracked) = cracked {
+            commands.entity(entity).insert(Visibility::Visible);
+
+            for (side_door, _, _) in side_doors.iter().filter(|(_, door, side_child_of)| {
+                door.id == cracked.id && side_child_of.parent() == child_of.parent()
+            }) {
+                commands.entity(side_door).despawn();
+            }
+        }
+    }
 }
 
 fn add_tile_collision(
@@ -564,3 +587,16 @@ fn build_colliders_from_vec2(mut positions: Vec<Vec2>, tile_size: f32) -> Vec<(V
 
     output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_level_has_bounds() {
+        for level in LevelId::ALL {
+            let rect = level.rect();
+            assert_eq!(LevelId::containing(rect.center()), Some(level));
+        }
+    }
+}
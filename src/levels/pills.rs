@@ -6,14 +6,14 @@ use bevy_ldtk_scene::levels::Level;
 use bevy_ldtk_scene::prelude::LevelMetaExt;
 use bevy_optix::zorder::YOrigin;
 use bevy_seedling::prelude::*;
-use bevy_sequence::combinators::delay::run_after;
 
+use crate::flags::StoryFlags;
 use crate::interactions::{Interactable, Interacted};
-use crate::inventory::item::{InventoryItem, ItemPickupEvent};
-use crate::levels::DoorDisabled;
+use crate::inventory::item::{self, InventoryItem, ItemPickupEvent, PILLS};
+use crate::levels::door::{CrackedDoors, DoorDisabled};
 use crate::notes::NoteEvent;
+use crate::sequence::run_after;
 use crate::textbox::{TextBlurb, TextboxEvent};
-use crate::world::SideDoor1;
 use crate::{Layer, world};
 
 pub struct PillsPlugin;
@@ -42,9 +42,6 @@ fn start(
         return;
     }
 
-    commands.run_system_cached(crate::despawn_entities::<With<PillState>>);
-    commands.spawn(PillState(0));
-
     commands.spawn((
         crate::audio::MusicPool,
         SamplePlayer::new(server.load("audio/music/quiet-halls.ogg"))
@@ -62,8 +59,8 @@ fn start(
     );
 }
 
-#[derive(Component)]
-struct PillState(usize);
+/// How many bottles of pills have been thrown away.
+pub const PILLS_DISPOSED: &str = "pills_disposed";
 
 #[derive(Default, Component)]
 #[require(Visibility::Hidden, DoorDisabled, YOrigin(-12.))]
@@ -78,9 +75,6 @@ struct CrackedDoor;
 )]
 struct Pills;
 
-#[derive(Component)]
-struct CollectedPills;
-
 fn pills(
     trigger: Trigger<OnAdd, Interacted>,
     pills: Query<&world::Pills>,
@@ -98,15 +92,7 @@ fn pills(
     )));
 
     commands.entity(trigger.target()).despawn();
-    let item = commands
-        .spawn((
-            CollectedPills,
-            InventoryItem {
-                name: "Pills".into(),
-                description: "Half full bottle of pills.".into(),
-            },
-        ))
-        .id();
+    let item = commands.spawn(item::pills()).id();
     writer.write(ItemPickupEvent(item));
 }
 
@@ -121,20 +107,26 @@ struct Trash;
 fn trash(
     trigger: Trigger<OnAdd, Interacted>,
     trash: Query<&Trash>,
-    pills: Query<Entity, With<CollectedPills>>,
+    items: Query<(Entity, &InventoryItem)>,
 
     mut commands: Commands,
     server: Res<AssetServer>,
     mut writer: EventWriter<TextboxEvent>,
 
-    id: Single<&PillState>,
+    flags: Res<StoryFlags>,
 ) {
     if !trash.get(trigger.target()).is_ok() {
         return;
     }
 
+    let pills = items
+        .iter()
+        .filter(|(_, item)| item.name == PILLS)
+        .map(|(entity, _)| entity)
+        .collect::<Vec<_>>();
+
     if pills.is_empty() {
-        if id.0 == 0 {
+        if !flags.is_set(PILLS_DISPOSED) {
             writer.write(TextboxEvent::section(TextBlurb::main_character(
                 "Where are the pills?",
             )));
@@ -149,7 +141,7 @@ fn trash(
         ..Default::default()
     });
 
-    for entity in pills.iter() {
+    for entity in pills {
         commands.entity(entity).despawn();
     }
 
@@ -157,41 +149,184 @@ fn trash(
 
     run_after(
         Duration::from_secs(1),
-        |mut commands: Commands,
-         server: Res<AssetServer>,
-         cracked_door: Query<(Entity, &world::CrackedSideDoor1)>,
-         side_door: Query<(Entity, &SideDoor1)>,
-         mut id: Single<&mut PillState>| {
-            for (entity, _) in side_door
-                .iter()
-                .filter(|(_, door)| door.id as usize == id.0)
-            {
-                commands.entity(entity).despawn();
-            }
-            for (entity, _) in cracked_door
-                .iter()
-                .filter(|(_, door)| door.id as usize == id.0)
-            {
-                commands.entity(entity).remove::<DoorDisabled>().insert((
-                    Visibility::Visible,
-                    // spatializing sound on door
-                    PlaybackSettings {
-                        on_complete: OnComplete::Remove,
-                        ..Default::default()
-                    },
-                    SamplePlayer {
-                        sample: server.load("audio/sfx/door-open.wav"),
-                        //volume: Volume::Linear(1.25),
-                        ..Default::default()
-                    },
-                    crate::audio::SpatialPool,
-                ));
-            }
-
-            info!("id: {}", id.0);
+        |mut doors: CrackedDoors, mut flags: ResMut<StoryFlags>| {
+            let id = flags.get(PILLS_DISPOSED);
+            info!("id: {id}");
 
-            id.0 += 1;
+            doors.reveal(id);
+            flags.increment(PILLS_DISPOSED);
         },
         &mut commands,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backlog::DialogueHistory;
+    use crate::reset::ResetAppExt;
+    use crate::testing::TestApp;
+    use crate::textbox::{TextboxFinished, TextboxPriority};
+
+    #[test]
+    fn disposing_pills_reveals_cracked_door() {
+        let mut app = TestApp::new(PillsPlugin);
+        app.spawn_player(Vec2::ZERO);
+
+        let scene = app.world_mut();
+        let pills = scene
+            .spawn(world::Pills {
+                flavor: "My pills.".into(),
+            })
+            .id();
+        let trash = scene.spawn(world::PillTrash).id();
+        let door = scene
+            .spawn(world::CrackedSideDoor1 {
+                target: None,
+                id: 0,
+                x: 0.,
+                y: 0.,
+                load: String::new(),
+            })
+            .id();
+        app.update();
+        assert!(app.world().entity(door).contains::<DoorDisabled>());
+
+        app.touch(pills).interact().finish_dialogue();
+        assert!(app.has_item(PILLS));
+
+        app.touch(trash).interact().advance(1.5);
+        assert!(!app.has_item(PILLS));
+        assert_eq!(app.flags().get(PILLS_DISPOSED), 1);
+        assert!(!app.world().entity(door).contains::<DoorDisabled>());
+        assert!(app.audio().played("audio/sfx/pills.wav"));
+        assert!(app.audio().played("audio/sfx/door-open.wav"));
+    }
+
+    /// Stands in for the level the pills are in, which a reset despawns.
+    #[derive(Component)]
+    struct Scene;
+
+    #[derive(Debug, PartialEq)]
+    struct Playthrough {
+        picked_up: bool,
+        disposed: i64,
+        door_revealed: bool,
+        played_sfx: bool,
+    }
+
+    fn play_pills(app: &mut TestApp) -> Playthrough {
+        app.audio_mut().clear();
+        app.spawn_player(Vec2::ZERO);
+
+        let scene = app.world_mut();
+        let pills = scene
+            .spawn((
+                Scene,
+                world::Pills {
+                    flavor: "My pills.".into(),
+                },
+            ))
+            .id();
+        let trash = scene.spawn((Scene, world::PillTrash)).id();
+        let door = scene
+            .spawn((
+                Scene,
+                world::CrackedSideDoor1 {
+                    target: None,
+                    id: 0,
+                    x: 0.,
+                    y: 0.,
+                    load: String::new(),
+                },
+            ))
+            .id();
+        app.update();
+
+        app.touch(pills).interact().finish_dialogue();
+        let picked_up = app.has_item(PILLS);
+
+        app.touch(trash).interact().advance(1.5);
+        Playthrough {
+            picked_up,
+            disposed: app.flags().get(PILLS_DISPOSED),
+            door_revealed: !app.world().entity(door).contains::<DoorDisabled>(),
+            played_sfx: app.audio().played("audio/sfx/pills.wav")
+                && app.audio().played("audio/sfx/door-open.wav"),
+        }
+    }
+
+    #[test]
+    fn playing_again_after_reset_behaves_the_same() {
+        let mut app = TestApp::new(PillsPlugin);
+        app.despawn_on_reset::<Scene>();
+
+        let first = play_pills(&mut app);
+        assert_eq!(
+            first,
+            Playthrough {
+                picked_up: true,
+                disposed: 1,
+                door_revealed: true,
+                played_sfx: true,
+            }
+        );
+
+        app.reset();
+        assert_eq!(app.flags().get(PILLS_DISPOSED), 0);
+        assert!(app.has_item("Pencil"));
+        assert!(!app.textbox_open());
+
+        let second = play_pills(&mut app);
+        assert_eq!(first, second);
+    }
+
+    #[derive(Default, Resource)]
+    struct Finished(Vec<TextboxFinished>);
+
+    #[test]
+    fn flavor_text_during_pickup_is_queued() {
+        let mut app = TestApp::new(PillsPlugin);
+        app.init_resource::<Finished>().add_systems(
+            Last,
+            |mut reader: EventReader<TextboxFinished>, mut finished: ResMut<Finished>| {
+                finished.0.extend(reader.read().copied());
+            },
+        );
+        app.spawn_player(Vec2::ZERO);
+
+        let pills = app
+            .world_mut()
+            .spawn(world::Pills {
+                flavor: "My pills.".into(),
+            })
+            .id();
+
+        // Stands in for an `Interaction` overlapping the pills, whose
+        // flavor text fires in the same frame as the pickup text.
+        app.add_observer(
+            |_: Trigger<OnAdd, Interacted>, mut writer: EventWriter<TextboxEvent>| {
+                writer.write_batch(["My pills.", "They rattle."].map(|line| {
+                    TextboxEvent::section(TextBlurb::main_character(line))
+                        .with_priority(TextboxPriority::Flavor)
+                }));
+            },
+        );
+        app.update();
+
+        app.touch(pills).interact().finish_dialogue();
+        assert!(app.has_item(PILLS));
+
+        let lines = app
+            .world()
+            .resource::<DialogueHistory>()
+            .iter()
+            .map(|entry| entry.text.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(lines, ["My pills.", "They rattle."]);
+
+        let finished = &app.world().resource::<Finished>().0;
+        assert_eq!(finished.len(), 3);
+        assert!(finished.iter().all(|finished| !finished.interrupted));
+    }
+}
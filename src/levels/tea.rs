@@ -1,4 +1,5 @@
-use super::DoorDisabled;
+use super::door::CrackedDoors;
+use crate::flags::{StoryFlags, on_flag};
 use crate::sequence::{ObserverSequence, con, delay};
 use crate::{
     animation::AnimationSprite,
@@ -9,7 +10,6 @@ use crate::{
 use avian2d::prelude::*;
 use bevy::prelude::*;
 use bevy_optix::zorder::YOrigin;
-use bevy_seedling::prelude::*;
 use bevy_sequence::prelude::*;
 
 pub struct TeaPlugin;
@@ -50,8 +50,8 @@ fn spawn_luna(
     Ok(())
 }
 
-#[derive(Event)]
-struct CutsceneDone;
+/// The tea cutscene has played to the end.
+pub const TEA_DONE: &str = "tea_done";
 
 pub fn tea_sequence() -> impl IntoFragment<ObserverSequence> {
     (
@@ -73,46 +73,14 @@ pub fn tea_sequence() -> impl IntoFragment<ObserverSequence> {
 
                 // spawn the cutscene
                 crate::cutscenes::tea::tea_cutscene()
-                    .on_end(|mut commands: Commands| commands.trigger(CutsceneDone))
+                    .on_end(|mut flags: ResMut<StoryFlags>| flags.set(TEA_DONE))
                     .spawn_box(&mut commands);
 
                 Ok(true)
             },
         ),
-        con(|_: Trigger<CutsceneDone>| ()),
-        delay(5.0).on_end(
-            |mut commands: Commands,
-             server: Res<AssetServer>,
-             cracked_door: Query<(Entity, &world::CrackedSideDoor1)>,
-             side_door: Query<(Entity, &world::SideDoor1)>| {
-                for (entity, _) in side_door
-                    .iter()
-                    .filter(|(_, door)| door.id as usize == 8392)
-                {
-                    commands.entity(entity).despawn();
-                }
-
-                for (entity, _) in cracked_door
-                    .iter()
-                    .filter(|(_, door)| door.id as usize == 8392)
-                {
-                    commands.entity(entity).remove::<DoorDisabled>().insert((
-                        Visibility::Visible,
-                        // spatializing sound on door
-                        PlaybackSettings {
-                            on_complete: OnComplete::Remove,
-                            ..Default::default()
-                        },
-                        SamplePlayer {
-                            sample: server.load("audio/sfx/door-open.wav"),
-                            //volume: Volume::Linear(1.25),
-                            ..Default::default()
-                        },
-                        crate::audio::SpatialPool,
-                    ));
-                }
-            },
-        ),
+        on_flag(TEA_DONE),
+        delay(5.0).on_end(|mut doors: CrackedDoors| doors.reveal(8392)),
     )
         .always()
         .once()
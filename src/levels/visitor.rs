@@ -11,24 +11,24 @@ use bevy_optix::camera::{MainCamera, PixelSnap};
 use bevy_optix::post_process::PostProcessCommand;
 use bevy_optix::zorder::YOrigin;
 use bevy_seedling::prelude::*;
-use bevy_sequence::combinators::delay::run_after;
 use bevy_sequence::prelude::*;
 
 use crate::animation::{AnimationAppExt, AnimationSprite};
 use crate::audio::SpatialPool;
 use crate::cutscene::fragments::IntoBox;
+use crate::flags::StoryFlags;
 use crate::interactions::{Interactable, Interacted};
 use crate::player::{PLAYER_SPEED, Player, Scaled};
+use crate::sequence::{rooted, run_after};
 use crate::{Layer, world};
 
-use super::DoorDisabled;
+use super::door::{CrackedDoors, DoorSprites, DoorState};
 
 pub struct VisitorPlugin;
 
 impl Plugin for VisitorPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<VisitorEvent>()
-            .register_required_components::<world::FrontDoor, FrontDoor>()
+        app.register_required_components::<world::FrontDoor, FrontDoor>()
             .register_required_components::<world::LunaRun, LunaRun>()
             .register_required_components_with::<world::Bush, _>(|| YOrigin(-24.))
             .register_required_components::<world::Tree, Tree>()
@@ -38,7 +38,7 @@ impl Plugin for VisitorPlugin {
                 "textures/luna.png",
                 TextureAtlasLayout::from_grid(UVec2::splat(48), 12, 8, None, None),
             )
-            .add_systems(Update, (visitor, front_door_sprite, luna_run))
+            .add_systems(Update, luna_run)
             .add_observer(start)
             .add_observer(front_door)
             .add_observer(tree_talk)
@@ -56,10 +56,8 @@ fn start(trigger: Trigger<OnAdd, Level>, levels: Query<&Level>, mut commands: Co
 
     run_after(
         Duration::from_secs(5),
-        |mut commands: Commands,
-         server: Res<AssetServer>,
-         mut writer: EventWriter<VisitorEvent>| {
-            writer.write(VisitorEvent(friendly_neighbor));
+        |mut commands: Commands, server: Res<AssetServer>, mut flags: ResMut<StoryFlags>| {
+            flags.set(VISITOR_KNOCKING);
             commands.spawn((
                 Knocking,
                 SamplePlayer::new(server.load("audio/sfx/knocking.wav"))
@@ -187,29 +185,7 @@ fn tree_man_scene(commands: &mut Commands) {
         .on_end(|mut commands: Commands| {
             run_after(
                 Duration::from_secs(5),
-                |mut commands: Commands,
-                 server: Res<AssetServer>,
-                 cracked_door: Query<(Entity, &world::CrackedSideDoor1)>| {
-                    for (entity, _) in cracked_door
-                        .iter()
-                        .filter(|(_, door)| door.id as usize == 222)
-                    {
-                        commands.entity(entity).remove::<DoorDisabled>().insert((
-                            Visibility::Visible,
-                            // spatializing sound on door
-                            PlaybackSettings {
-                                on_complete: OnComplete::Remove,
-                                ..Default::default()
-                            },
-                            SamplePlayer {
-                                sample: server.load("audio/sfx/door-open.wav"),
-                                //volume: Volume::Linear(1.25),
-                                ..Default::default()
-                            },
-                            crate::audio::SpatialPool,
-                        ));
-                    }
-                },
+                |mut doors: CrackedDoors| doors.reveal(222),
                 &mut commands,
             );
         })
@@ -220,41 +196,12 @@ fn tree_man_scene(commands: &mut Commands) {
 struct Knocking;
 
 #[derive(Default, Component)]
-#[require(Interactable, DoorState)]
+#[require(
+    Interactable,
+    DoorSprites::new("textures/front-door.png", Vec2::new(16., 32.))
+)]
 struct FrontDoor;
 
-#[derive(Default, Component)]
-enum DoorState {
-    #[default]
-    Closed,
-    Open,
-}
-
-fn front_door_sprite(
-    mut commands: Commands,
-    server: Res<AssetServer>,
-    entities: Query<(Entity, &DoorState), Changed<DoorState>>,
-) {
-    for (entity, state) in entities.iter() {
-        match state {
-            DoorState::Closed => {
-                commands.entity(entity).insert(Sprite {
-                    image: server.load("textures/front-door.png"),
-                    rect: Some(Rect::from_corners(Vec2::new(0., 0.), Vec2::new(16., 32.))),
-                    ..Default::default()
-                });
-            }
-            DoorState::Open => {
-                commands.entity(entity).insert(Sprite {
-                    image: server.load("textures/front-door.png"),
-                    rect: Some(Rect::from_corners(Vec2::new(16., 0.), Vec2::new(32., 32.))),
-                    ..Default::default()
-                });
-            }
-        }
-    }
-}
-
 #[derive(Component)]
 struct TheDoor;
 
@@ -267,12 +214,17 @@ fn front_door(
 
     mut commands: Commands,
     server: Res<AssetServer>,
-    visitor: Single<(Entity, &VisitorEvent)>,
+    mut flags: ResMut<StoryFlags>,
 ) {
-    if front_door.get(trigger.target()).is_err() {
+    if front_door.get(trigger.target()).is_err()
+        || !flags.is_set(VISITOR_KNOCKING)
+        || flags.is_set(VISITOR_ANSWERED)
+    {
         return;
     };
 
+    flags.set(VISITOR_ANSWERED);
+
     commands.spawn((
         NightSfx,
         SamplePlayer::new(server.load("audio/sfx/night.ogg"))
@@ -285,22 +237,13 @@ fn front_door(
         .entity(trigger.target())
         .insert((DoorState::Open, TheDoor));
 
-    let (entity, visitor) = visitor.into_inner();
-    commands.entity(entity).despawn();
-
-    visitor.0(&mut commands);
+    friendly_neighbor(&mut commands);
 }
 
-#[derive(Clone, Copy, Event, Component)]
-struct VisitorEvent(fn(&mut Commands));
-
-fn visitor(mut commands: Commands, mut reader: EventReader<VisitorEvent>) {
-    debug_assert!(reader.len() <= 1);
-
-    for event in reader.read() {
-        commands.spawn(*event);
-    }
-}
+/// Someone is knocking at the front door.
+pub const VISITOR_KNOCKING: &str = "visitor_knocking";
+/// The front door has been opened for the visitor.
+pub const VISITOR_ANSWERED: &str = "visitor_answered";
 
 fn friendly_neighbor(commands: &mut Commands) {
     crate::cutscenes::visitor::visitor()
@@ -317,7 +260,7 @@ fn friendly_neighbor(commands: &mut Commands) {
                     },
                 ));
 
-                spawn_root(super::tea::tea_sequence(), &mut commands);
+                spawn_root(rooted(super::tea::tea_sequence()), &mut commands);
 
                 commands.spawn(SamplePlayer::new(server.load("audio/sfx/door-close.wav")));
                 commands.run_system_cached(crate::despawn_entities::<With<NightSfx>>);
@@ -1,4 +1,6 @@
 use crate::GameState;
+use crate::cli::LaunchOptions;
+use crate::cutscene::script::DialogueScript;
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 
@@ -6,16 +8,16 @@ pub struct LoadingPlugin;
 
 impl Plugin for LoadingPlugin {
     fn build(&self, app: &mut App) {
-        #[cfg(not(debug_assertions))]
-        let cont = GameState::Menu;
-
-        #[cfg(debug_assertions)]
-        let cont = GameState::Playing;
+        let next = match app.world().get_resource::<LaunchOptions>() {
+            Some(options) if options.starts_directly() => GameState::Playing,
+            _ => GameState::Menu,
+        };
 
         app.add_loading_state(
             LoadingState::new(GameState::Loading)
-                .continue_to_state(cont)
-                .load_collection::<TextureAssets>(),
+                .continue_to_state(next)
+                .load_collection::<TextureAssets>()
+                .load_collection::<DialogueAssets>(),
         );
     }
 }
@@ -27,3 +29,37 @@ pub struct TextureAssets {
     //#[asset(path = "textures/github.png")]
     //pub github: Handle<Image>,
 }
+
+/// Dialogue scripts are loaded up front so cutscenes never wait on them.
+#[derive(AssetCollection, Resource)]
+pub struct DialogueAssets {
+    #[asset(path = "dialogue/visitor.dialogue.ron")]
+    pub visitor: Handle<DialogueScript>,
+    #[asset(path = "dialogue/park.dialogue.ron")]
+    pub park: Handle<DialogueScript>,
+    #[asset(path = "dialogue/park-man-one.dialogue.ron")]
+    pub park_man_one: Handle<DialogueScript>,
+    #[asset(path = "dialogue/park-man-two.dialogue.ron")]
+    pub park_man_two: Handle<DialogueScript>,
+    #[asset(path = "dialogue/tea.dialogue.ron")]
+    pub tea: Handle<DialogueScript>,
+    #[asset(path = "dialogue/sturgeon.dialogue.ron")]
+    pub sturgeon: Handle<DialogueScript>,
+    #[asset(path = "dialogue/dark-home.dialogue.ron")]
+    pub dark_home: Handle<DialogueScript>,
+    #[asset(
+        paths(
+            "dialogue/shadow-1.dialogue.ron",
+            "dialogue/shadow-2.dialogue.ron",
+            "dialogue/shadow-3.dialogue.ron",
+            "dialogue/shadow-4.dialogue.ron",
+            "dialogue/shadow-5.dialogue.ron",
+            "dialogue/shadow-6.dialogue.ron",
+            "dialogue/shadow-7.dialogue.ron",
+            "dialogue/shadow-8.dialogue.ron",
+            "dialogue/shadow-9.dialogue.ron"
+        ),
+        collection(typed)
+    )]
+    pub shadows: Vec<Handle<DialogueScript>>,
+}
@@ -12,15 +12,23 @@ use bevy::prelude::*;
 use bevy::window::{PrimaryWindow, WindowResolution};
 use bevy::winit::WinitWindows;
 use bevy_optix::pixel_perfect::CanvasDimensions;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 use std::io::Cursor;
 use winit::window::Icon;
 
 mod animation;
 mod audio;
+mod backlog;
 mod callback;
+mod cli;
+mod controls;
+mod credits;
 mod cutscene;
 mod cutscenes;
+mod ending;
 mod entities;
+mod flags;
 mod fragments;
 mod hook;
 mod interactions;
@@ -30,8 +38,14 @@ mod loading;
 mod menu;
 mod notes;
 mod player;
+mod reset;
+mod save;
 mod sequence;
+mod settings;
+#[cfg(test)]
+mod testing;
 mod textbox;
+mod widgets;
 #[allow(unused)]
 mod world;
 
@@ -42,8 +56,17 @@ pub const RESOLUTION_SCALE: f32 = 5.;
 pub const TILE_SIZE: f32 = 16.;
 
 fn main() {
+    let options = cli::LaunchOptions::from_env();
+    let resolution = match options.windowed {
+        Some(size) => WindowResolution::new(size.x as f32, size.y as f32),
+        None => WindowResolution::new(WIDTH * RESOLUTION_SCALE, HEIGHT * RESOLUTION_SCALE),
+    };
+
     let mut app = App::new();
 
+    // Plugins read these while they're built.
+    app.insert_resource(options);
+
     app.add_plugins((
         DefaultPlugins
             .set(WindowPlugin {
@@ -52,10 +75,7 @@ fn main() {
                     title: "Time Marches On".to_string(),
                     fit_canvas_to_parent: true,
                     prevent_default_event_handling: false,
-                    resolution: WindowResolution::new(
-                        WIDTH * RESOLUTION_SCALE,
-                        HEIGHT * RESOLUTION_SCALE,
-                    ),
+                    resolution,
                     ..default()
                 }),
                 ..default()
@@ -107,31 +127,48 @@ fn main() {
         audio::AudioPlugin,
         sequence::ObserverSequencePlugin,
     ))
+    .add_plugins((
+        save::SavePlugin,
+        flags::FlagsPlugin,
+        backlog::BacklogPlugin,
+        settings::SettingsPlugin,
+        controls::ControlsPlugin,
+        credits::CreditsPlugin,
+        ending::EndingPlugin,
+        reset::ResetPlugin,
+        cli::CliPlugin,
+        widgets::WidgetsPlugin,
+    ))
     .init_state::<GameState>()
+    .init_resource::<GameRng>()
     .add_sub_state::<PlayingState>()
     .init_schedule(Avian)
     .insert_resource(Gravity(Vec2::ZERO))
     .add_systems(Startup, set_window_icon);
 
-    #[cfg(not(feature = "web-audio"))]
-    app.add_plugins(bevy_seedling::SeedlingPlugin::default());
+    if audio::null::requested() {
+        app.add_plugins(audio::null::NullAudioPlugin);
+    } else {
+        #[cfg(not(feature = "web-audio"))]
+        app.add_plugins(bevy_seedling::SeedlingPlugin::default());
 
-    #[cfg(feature = "web-audio")]
-    app.add_plugins(
-        bevy_seedling::SeedlingPlugin::<firewheel_web_audio::WebAudioBackend> {
-            config: Default::default(),
-            stream_config: Default::default(),
-            spawn_default_pool: true,
-            pool_size: 4..=32,
-        },
-    );
+        #[cfg(feature = "web-audio")]
+        app.add_plugins(
+            bevy_seedling::SeedlingPlugin::<firewheel_web_audio::WebAudioBackend> {
+                config: Default::default(),
+                stream_config: Default::default(),
+                spawn_default_pool: true,
+                pool_size: 4..=32,
+            },
+        );
+    }
 
     app.world_mut()
         .resource_mut::<FixedMainScheduleOrder>()
         .insert_after(FixedPostUpdate, Avian);
 
     #[cfg(debug_assertions)]
-    app.add_systems(Update, close_on_escape);
+    app.add_systems(Update, close_on_shift_escape);
     #[cfg(debug_assertions)]
     app.add_plugins(avian2d::debug_render::PhysicsDebugPlugin::new(Avian))
         .add_systems(Update, enable_avian_debug);
@@ -151,6 +188,10 @@ enum GameState {
     Menu,
     Hook,
     Playing,
+    /// The story is over and the world is being torn down.
+    Ending,
+    /// The credits roll, after which the game returns to the menu.
+    Credits,
 }
 
 #[derive(SubStates, Default, Clone, Eq, PartialEq, Debug, Hash)]
@@ -172,6 +213,22 @@ pub enum Layer {
     Other,
 }
 
+/// The source of randomness for gameplay, seeded with `--seed`.
+#[derive(Resource, Deref, DerefMut)]
+pub struct GameRng(StdRng);
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
+impl GameRng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
 pub struct HexColor(pub u32);
 
 impl Into<Color> for HexColor {
@@ -214,8 +271,9 @@ fn set_window_icon(
 }
 
 #[cfg(debug_assertions)]
-fn close_on_escape(input: Res<ButtonInput<KeyCode>>, mut writer: EventWriter<AppExit>) {
-    if input.just_pressed(KeyCode::Escape) {
+fn close_on_shift_escape(input: Res<ButtonInput<KeyCode>>, mut writer: EventWriter<AppExit>) {
+    // Plain escape pauses, so quitting needs shift as well.
+    if input.pressed(KeyCode::ShiftLeft) && input.just_pressed(KeyCode::Escape) {
         writer.write(AppExit::Success);
     }
 }
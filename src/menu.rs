@@ -1,204 +1,223 @@
+use bevy::{
+    input_focus::{InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+use bevy_enhanced_input::prelude::Actions;
+
 use crate::GameState;
-use bevy::prelude::*;
+use crate::ending::Completion;
+use crate::inventory::input::InventoryContext;
+use crate::levels::chapter::{Checkpoints, OpenChapters};
+use crate::reset::ResetWorld;
+use crate::save::{LoadEvent, SavePath};
+use crate::settings::OpenSettings;
+use crate::widgets::{self, Disabled};
 
 pub struct MenuPlugin;
 
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(GameState::Menu), setup_menu)
-            .add_systems(Update, click_play_button.run_if(in_state(GameState::Menu)))
-            .add_systems(OnExit(GameState::Menu), cleanup_menu);
+            .add_systems(OnExit(GameState::Menu), cleanup_menu)
+            .add_observer(click_button);
     }
 }
 
 #[derive(Component)]
-struct ButtonColors {
-    normal: Color,
-    hovered: Color,
+struct Menu;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+enum MenuButton {
+    NewGame,
+    Continue,
+    Chapters,
+    Settings,
+    Credits,
+    #[cfg(not(target_arch = "wasm32"))]
+    Quit,
 }
 
-impl Default for ButtonColors {
-    fn default() -> Self {
-        ButtonColors {
-            normal: Color::linear_rgb(0.15, 0.15, 0.15),
-            hovered: Color::linear_rgb(0.25, 0.25, 0.25),
+impl MenuButton {
+    fn label(&self) -> &'static str {
+        match self {
+            Self::NewGame => "New Game",
+            Self::Continue => "Continue",
+            Self::Chapters => "Chapters",
+            Self::Settings => "Settings",
+            Self::Credits => "Credits",
+            #[cfg(not(target_arch = "wasm32"))]
+            Self::Quit => "Quit",
         }
     }
 }
 
-#[derive(Component)]
-struct Menu;
+fn setup_menu(
+    mut commands: Commands,
+    mut directional_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    save: Res<SavePath>,
+    checkpoints: Res<Checkpoints>,
+    server: Res<AssetServer>,
+) {
+    commands.spawn((Menu, Camera2d, Msaa::Off));
 
-fn setup_menu(mut commands: Commands) {
-    commands.spawn((Camera2d, Msaa::Off));
-    commands
+    let font = server.load("fonts/raster-forge.ttf");
+    let root = commands
         .spawn((
+            Menu,
+            Actions::<InventoryContext>::default(),
+            BackgroundColor(Color::BLACK),
             Node {
                 width: Val::Percent(100.0),
                 height: Val::Percent(100.0),
                 flex_direction: FlexDirection::Column,
                 align_items: AlignItems::Center,
                 justify_content: JustifyContent::Center,
+                row_gap: Val::Px(10.0),
                 ..default()
             },
-            Menu,
-        ))
-        .with_children(|children| {
-            let button_colors = ButtonColors::default();
-            children
-                .spawn((
-                    Button,
+            children![
+                (
+                    ImageNode::new(server.load("textures/face.png")),
                     Node {
-                        width: Val::Px(140.0),
-                        height: Val::Px(50.0),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        ..Default::default()
-                    },
-                    BackgroundColor(button_colors.normal),
-                    button_colors,
-                    ChangeState(GameState::Hook),
-                ))
-                .with_child((
-                    Text::new("Play"),
-                    TextFont {
-                        font_size: 40.0,
+                        width: Val::Px(crate::WIDTH * 2.0),
+                        height: Val::Px(crate::HEIGHT * 2.0),
                         ..default()
                     },
-                    TextColor(Color::linear_rgb(0.9, 0.9, 0.9)),
-                ));
-        });
-    commands
-        .spawn((
-            Node {
-                flex_direction: FlexDirection::Row,
-                align_items: AlignItems::Center,
-                justify_content: JustifyContent::SpaceAround,
-                bottom: Val::Px(5.),
-                width: Val::Percent(100.),
-                position_type: PositionType::Absolute,
-                ..default()
-            },
-            Menu,
-        ))
-        .with_children(|children| {
-            children
-                .spawn((
-                    Button,
-                    Node {
-                        width: Val::Px(170.0),
-                        height: Val::Px(50.0),
-                        justify_content: JustifyContent::SpaceAround,
-                        align_items: AlignItems::Center,
-                        padding: UiRect::all(Val::Px(5.)),
+                ),
+                (
+                    Text::new("Time Marches On"),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 64.0,
                         ..Default::default()
                     },
-                    BackgroundColor(Color::NONE),
-                    ButtonColors {
-                        normal: Color::NONE,
-                        ..default()
-                    },
-                    OpenLink("https://bevyengine.org"),
-                ))
-                .with_children(|parent| {
-                    parent.spawn((
-                        Text::new("Made with Bevy"),
-                        TextFont {
-                            font_size: 15.0,
-                            ..default()
-                        },
-                        TextColor(Color::linear_rgb(0.9, 0.9, 0.9)),
-                    ));
-                    parent.spawn((
-                        //ImageNode {
-                        //    image: textures.bevy.clone(),
-                        //    ..default()
-                        //},
-                        Node {
-                            width: Val::Px(32.),
-                            ..default()
-                        },
-                    ));
-                });
-            children
-                .spawn((
-                    Button,
                     Node {
-                        width: Val::Px(170.0),
-                        height: Val::Px(50.0),
-                        justify_content: JustifyContent::SpaceAround,
-                        align_items: AlignItems::Center,
-                        padding: UiRect::all(Val::Px(5.)),
+                        margin: UiRect::vertical(Val::Px(20.0)),
                         ..default()
                     },
-                    BackgroundColor(Color::NONE),
-                    ButtonColors {
-                        normal: Color::NONE,
-                        hovered: Color::linear_rgb(0.25, 0.25, 0.25),
-                    },
-                    OpenLink("https://github.com/NiklasEi/bevy_game_template"),
-                ))
-                .with_children(|parent| {
-                    parent.spawn((
-                        Text::new("Open source"),
-                        TextFont {
-                            font_size: 15.0,
-                            ..default()
-                        },
-                        TextColor(Color::linear_rgb(0.9, 0.9, 0.9)),
-                    ));
-                    parent.spawn((
-                        //ImageNode::new(textures.github.clone()),
-                        Node {
-                            width: Val::Px(32.),
-                            ..default()
-                        },
-                    ));
-                });
-        });
+                ),
+            ],
+        ))
+        .id();
+
+    let endings = Completion::load().endings;
+    if endings > 0 {
+        commands.spawn((
+            Text::new(completion_label(endings)),
+            TextFont {
+                font: font.clone(),
+                font_size: 32.0,
+                ..Default::default()
+            },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            Node {
+                margin: UiRect::bottom(Val::Px(10.0)),
+                ..default()
+            },
+            ChildOf(root),
+        ));
+    }
+
+    let has_save = save.exists();
+    let buttons = [
+        MenuButton::NewGame,
+        MenuButton::Continue,
+        MenuButton::Chapters,
+        MenuButton::Settings,
+        MenuButton::Credits,
+        #[cfg(not(target_arch = "wasm32"))]
+        MenuButton::Quit,
+    ];
+
+    let mut enabled = Vec::new();
+    for action in buttons {
+        let mut button = commands.spawn((menu_button(action, &font), ChildOf(root)));
+
+        let disabled = match action {
+            MenuButton::Continue => !has_save,
+            // The first chapter is the same as a new game.
+            MenuButton::Chapters => checkpoints.unlocked.len() < 2,
+            _ => false,
+        };
+
+        if disabled {
+            button.insert(Disabled);
+        } else {
+            enabled.push(button.id());
+        }
+    }
+
+    directional_nav_map.add_looping_edges(&enabled, CompassOctant::South);
+
+    // Continuing is the likelier choice once there's a save.
+    let initial = if has_save { enabled[1] } else { enabled[0] };
+    input_focus.set(initial);
 }
 
-#[derive(Component)]
-struct ChangeState(GameState);
+/// Marks the title once the story has been finished on this machine.
+fn completion_label(endings: u32) -> String {
+    match endings {
+        1 => "Finished once".into(),
+        n => format!("Finished {n} times"),
+    }
+}
 
-#[derive(Component)]
-struct OpenLink(&'static str);
+fn menu_button(action: MenuButton, font: &Handle<Font>) -> impl Bundle {
+    (
+        action,
+        widgets::button(
+            action.label(),
+            40.0,
+            Node {
+                width: Val::Px(260.0),
+                height: Val::Px(50.0),
+                ..default()
+            },
+            font,
+        ),
+    )
+}
 
-fn click_play_button(
+fn click_button(
+    trigger: Trigger<Pointer<Click>>,
+    buttons: Query<&MenuButton, Without<Disabled>>,
     mut next_state: ResMut<NextState<GameState>>,
-    mut interaction_query: Query<
-        (
-            &Interaction,
-            &mut BackgroundColor,
-            &ButtonColors,
-            Option<&ChangeState>,
-            Option<&OpenLink>,
-        ),
-        (Changed<Interaction>, With<Button>),
-    >,
+    mut load: EventWriter<LoadEvent>,
+    mut reset: EventWriter<ResetWorld>,
+    mut commands: Commands,
 ) {
-    for (interaction, mut color, button_colors, change_state, open_link) in &mut interaction_query {
-        match *interaction {
-            Interaction::Pressed => {
-                if let Some(state) = change_state {
-                    next_state.set(state.0.clone());
-                } else if let Some(link) = open_link {
-                    if let Err(error) = webbrowser::open(link.0) {
-                        warn!("Failed to open link {error:?}");
-                    }
-                }
-            }
-            Interaction::Hovered => {
-                *color = button_colors.hovered.into();
-            }
-            Interaction::None => {
-                *color = button_colors.normal.into();
-            }
+    let Ok(button) = buttons.get(trigger.target()) else {
+        return;
+    };
+
+    match button {
+        MenuButton::NewGame => {
+            reset.write_default();
+            next_state.set(GameState::Hook);
+        }
+        MenuButton::Continue => {
+            load.write_default();
+        }
+        MenuButton::Chapters => commands.trigger(OpenChapters),
+        MenuButton::Settings => commands.trigger(OpenSettings),
+        MenuButton::Credits => next_state.set(GameState::Credits),
+        #[cfg(not(target_arch = "wasm32"))]
+        MenuButton::Quit => {
+            commands.send_event(AppExit::Success);
         }
     }
 }
 
-fn cleanup_menu(mut commands: Commands, menu: Query<Entity, With<Menu>>) {
+fn cleanup_menu(
+    mut commands: Commands,
+    menu: Query<Entity, With<Menu>>,
+    mut directional_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+) {
+    directional_nav_map.clear();
+    input_focus.clear();
     for entity in menu.iter() {
         commands.entity(entity).despawn();
     }
@@ -5,25 +5,60 @@ use bevy_enhanced_input::events::Fired;
 use bevy_enhanced_input::prelude::Actions;
 use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
 use bevy_seedling::prelude::*;
-use bevy_sequence::combinators::delay::run_after;
 use bevy_tween::interpolate::{sprite_color, translation};
 use bevy_tween::prelude::{AnimationBuilderExt, EaseKind};
 use bevy_tween::tween::IntoTarget;
+use serde::{Deserialize, Serialize};
 
 use crate::player::{self, InhibitAddEvent, InhibitRemoveEvent, Player, PlayerContext};
+use crate::reset::ResetAppExt;
+use crate::save::{Persistent, SaveAppExt};
+use crate::sequence::run_after;
 use crate::textbox::{Interact, TextboxContext};
 
 pub struct NotesPlugin;
 
 impl Plugin for NotesPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<NoteEvent>().add_systems(Update, note_event);
+        app.add_event::<NoteEvent>()
+            .add_event::<ReadNoteEvent>()
+            .init_resource::<Journal>()
+            .register_persistent::<Journal>()
+            .reset_resource::<Journal>()
+            .despawn_on_reset::<Note>()
+            .despawn_on_reset::<Entered>()
+            .add_systems(Update, note_event);
     }
 }
 
+/// Show a note for the first time, adding it to the [`Journal`].
 #[derive(Clone, Copy, Event)]
 pub struct NoteEvent(pub &'static str);
 
+/// Show the journal entry at the given index again.
+#[derive(Clone, Copy, Event)]
+pub struct ReadNoteEvent(pub usize);
+
+/// Every note the player has found, in pickup order.
+///
+/// Entries are image names relative to `textures/notes`.
+#[derive(Debug, Default, Clone, Resource, Serialize, Deserialize)]
+pub struct Journal {
+    pub notes: Vec<String>,
+}
+
+impl Persistent for Journal {
+    const KEY: &'static str = "journal";
+
+    fn capture(world: &mut World) -> Option<Self> {
+        world.get_resource::<Self>().cloned()
+    }
+
+    fn restore(self, world: &mut World) {
+        world.insert_resource(self);
+    }
+}
+
 const FADE_DUR: f32 = 0.2;
 const SLIDE_DUR: f32 = 1.;
 
@@ -42,16 +77,30 @@ struct Entered;
 fn note_event(
     mut commands: Commands,
     mut reader: EventReader<NoteEvent>,
+    mut reread: EventReader<ReadNoteEvent>,
+    mut journal: ResMut<Journal>,
     player: Single<Entity, With<Player>>,
     server: Res<AssetServer>,
 ) {
-    if reader.is_empty() {
+    let note = if let Some(note) = reader.read().next() {
+        debug_assert!(reader.is_empty());
+        reader.clear();
+
+        if !journal.notes.iter().any(|n| n == note.0) {
+            journal.notes.push(note.0.to_string());
+        }
+
+        note.0.to_string()
+    } else if let Some(entry) = reread.read().last() {
+        let Some(note) = journal.notes.get(entry.0) else {
+            error!("no journal entry at index {}", entry.0);
+            return;
+        };
+
+        note.clone()
+    } else {
         return;
-    }
-
-    let note = *reader.read().next().unwrap();
-    debug_assert!(reader.is_empty());
-    reader.clear();
+    };
 
     commands.spawn(Entered);
     commands.entity(*player).trigger(InhibitAddEvent);
@@ -83,7 +132,7 @@ fn note_event(
             Note,
             TheNote,
             HIGH_RES_LAYER,
-            Sprite::from_image(server.load(format!("textures/notes/{}", note.0))),
+            Sprite::from_image(server.load(format!("textures/notes/{note}"))),
             Transform::from_xyz(0., 0., 901.0).with_scale(Vec3::splat(crate::RESOLUTION_SCALE)),
         ))
         .animation()
@@ -10,8 +10,12 @@ use bevy_optix::camera::PixelSnap;
 use bevy_optix::zorder::YOrigin;
 use bevy_seedling::prelude::*;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
+use crate::GameRng;
 use crate::animation::{AnimationAppExt, AnimationController, AnimationSprite};
+use crate::reset::ResetAppExt;
+use crate::save::{Persistent, SaveAppExt};
 use crate::{Layer, world};
 
 pub const PLAYER_SPEED: f32 = 70.;
@@ -26,7 +30,12 @@ impl Plugin for PlayerPlugin {
                 TextureAtlasLayout::from_grid(UVec2::splat(48), 12, 8, None, None),
             )
             .register_required_components::<world::PlayerVessel, Player>()
-            .add_systems(Update, (scaled, play_footsteps))
+            .register_persistent::<PlayerPosition>()
+            .despawn_on_reset::<Player>()
+            .on_reset(|mut commands: Commands| {
+                commands.remove_resource::<PendingPlayerPosition>();
+            })
+            .add_systems(Update, (scaled, play_footsteps, restore_position))
             .add_observer(bind)
             .add_observer(apply_movement)
             .add_observer(stop_movement)
@@ -107,6 +116,7 @@ pub fn add_actions(mut commands: Commands, player: Single<Entity, With<Player>>)
     YOrigin(-12.),
     Scaled(Vec2::splat(0.8)),
     FootstepTimer(Timer::new(Duration::from_millis(750), TimerMode::Repeating)),
+    Walking,
     SpatialListener2D,
 )]
 #[component(on_insert = Self::bind_camera)]
@@ -130,6 +140,44 @@ impl Player {
     }
 }
 
+#[derive(Serialize, Deserialize)]
+struct PlayerPosition(Vec3);
+
+impl Persistent for PlayerPosition {
+    const KEY: &'static str = "player";
+
+    fn capture(world: &mut World) -> Option<Self> {
+        world
+            .query_filtered::<&Transform, With<Player>>()
+            .single(world)
+            .ok()
+            .map(|transform| Self(transform.translation))
+    }
+
+    fn restore(self, world: &mut World) {
+        world.insert_resource(PendingPlayerPosition(self.0));
+    }
+}
+
+/// A restored position applied to the next player that spawns.
+#[derive(Resource)]
+struct PendingPlayerPosition(Vec3);
+
+fn restore_position(
+    mut commands: Commands,
+    pending: Option<Res<PendingPlayerPosition>>,
+    mut player: Query<&mut Transform, Added<Player>>,
+) {
+    let Some(pending) = pending else {
+        return;
+    };
+
+    for mut transform in player.iter_mut() {
+        transform.translation = pending.0;
+        commands.remove_resource::<PendingPlayerPosition>();
+    }
+}
+
 #[derive(Component)]
 pub struct Scaled(pub Vec2);
 
@@ -180,27 +228,35 @@ fn apply_movement(
 #[derive(Component)]
 struct FootstepTimer(Timer);
 
+/// How the player was last moving.
+///
+/// This lives on the player rather than in the systems so a new player
+/// starts fresh after a reset.
+#[derive(Default, Component)]
+struct Walking {
+    moving: bool,
+    direction: Vec2,
+}
+
 fn play_footsteps(
-    player: Single<(&mut FootstepTimer, &LinearVelocity), With<Player>>,
+    player: Single<(&mut FootstepTimer, &mut Walking, &LinearVelocity), With<Player>>,
     mut commands: Commands,
     server: Res<AssetServer>,
     time: Res<Time>,
-    mut is_moving: Local<bool>,
+    mut rng: ResMut<GameRng>,
 ) {
-    let (mut timer, velocity) = player.into_inner();
+    let (mut timer, mut walking, velocity) = player.into_inner();
 
     if velocity.length() == 0.0 {
         timer.0.reset();
-        *is_moving = false;
+        walking.moving = false;
         return;
     }
 
-    let just_started = !*is_moving;
-    *is_moving = true;
+    let just_started = !walking.moving;
+    walking.moving = true;
 
     if timer.0.tick(time.delta()).just_finished() || just_started {
-        let mut rng = rand::thread_rng();
-
         let sample = if rng.gen_bool(0.5) {
             "audio/sfx/step1.wav"
         } else {
@@ -217,14 +273,15 @@ fn play_footsteps(
 fn move_sprite(
     trigger: Trigger<Fired<MoveAction>>,
     mut commands: Commands,
-    mut last: Local<Vec2>,
-    player: Single<Option<&AnimationController>, With<Player>>,
+    player: Single<(Option<&AnimationController>, &mut Walking), With<Player>>,
 ) {
+    let (animation, mut walking) = player.into_inner();
+
     let v = trigger.value;
-    if *last == v && player.is_some() {
+    if walking.direction == v && animation.is_some() {
         return;
     }
-    *last = v;
+    walking.direction = v;
 
     let dir = if v.x.abs() < v.y.abs() {
         Vec2::new(0.0, if v.y.is_sign_positive() { 1. } else { -1. })
@@ -240,7 +297,7 @@ fn move_sprite(
         _ => unreachable!(),
     };
 
-    if player.is_some_and(|player| player.indices.seq[0] == range[0]) {
+    if animation.is_some_and(|animation| animation.indices.seq[0] == range[0]) {
         return;
     }
 
@@ -0,0 +1,119 @@
+use bevy::ecs::system::SystemId;
+use bevy::prelude::*;
+
+pub struct ResetPlugin;
+
+impl Plugin for ResetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ResetRegistry>()
+            .add_event::<ResetWorld>()
+            .add_systems(Last, reset_on_event.after(crate::save::save_game));
+    }
+}
+
+/// Tear down the story and put every gameplay module back the way it was
+/// at launch, so a new game can start in the same process.
+///
+/// The reset runs at the end of the frame, after any save written that
+/// frame, so it's safe to send alongside a [`SaveEvent`](crate::save::SaveEvent)
+/// and a state change.
+#[derive(Debug, Default, Event)]
+pub struct ResetWorld;
+
+/// Gameplay modules register what a reset means for them, so modules
+/// never need to know about each other.
+///
+/// On reset, registered entities are despawned first, then registered
+/// resources are replaced with their defaults, and finally the
+/// [`ResetAppExt::on_reset`] systems run in registration order.
+pub trait ResetAppExt {
+    /// Despawn every entity with `C`, along with its descendants.
+    fn despawn_on_reset<C: Component>(&mut self) -> &mut Self;
+
+    /// Replace `R` with its default value.
+    fn reset_resource<R: Resource + Default>(&mut self) -> &mut Self;
+
+    /// Run `system` once entities are despawned and resources are reset.
+    fn on_reset<M>(&mut self, system: impl IntoSystem<(), (), M> + 'static) -> &mut Self;
+}
+
+impl ResetAppExt for App {
+    fn despawn_on_reset<C: Component>(&mut self) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<ResetRegistry>()
+            .despawn
+            .push(despawn::<C>);
+        self
+    }
+
+    fn reset_resource<R: Resource + Default>(&mut self) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<ResetRegistry>()
+            .resources
+            .push(reset_resource::<R>);
+        self
+    }
+
+    fn on_reset<M>(&mut self, system: impl IntoSystem<(), (), M> + 'static) -> &mut Self {
+        let system = self.world_mut().register_system(system);
+        self.world_mut()
+            .get_resource_or_init::<ResetRegistry>()
+            .systems
+            .push(system);
+        self
+    }
+}
+
+#[derive(Default, Resource)]
+struct ResetRegistry {
+    despawn: Vec<fn(&mut World)>,
+    resources: Vec<fn(&mut World)>,
+    systems: Vec<SystemId>,
+}
+
+fn despawn<C: Component>(world: &mut World) {
+    let entities = world
+        .query_filtered::<Entity, With<C>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+
+    for entity in entities {
+        if let Ok(entity) = world.get_entity_mut(entity) {
+            entity.despawn();
+        }
+    }
+}
+
+fn reset_resource<R: Resource + Default>(world: &mut World) {
+    world.insert_resource(R::default());
+}
+
+fn reset_on_event(mut reader: EventReader<ResetWorld>, mut commands: Commands) {
+    if reader.read().count() > 0 {
+        commands.queue(reset_world);
+    }
+}
+
+/// Run every registered reset immediately.
+pub fn reset_world(world: &mut World) {
+    info!("resetting the world");
+
+    let registry = world.resource::<ResetRegistry>();
+    let despawn = registry.despawn.clone();
+    let resources = registry.resources.clone();
+    let systems = registry.systems.clone();
+
+    for despawn in despawn {
+        despawn(world);
+    }
+
+    for reset in resources {
+        reset(world);
+    }
+
+    for system in systems {
+        if let Err(e) = world.run_system(system) {
+            error!("failed to run reset system: {e}");
+        }
+    }
+}
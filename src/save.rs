@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::GameState;
+
+/// Bumped whenever the layout of [`SaveFile`] changes in an incompatible way.
+pub const SAVE_VERSION: u32 = 1;
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveRegistry>()
+            .init_resource::<SavePath>()
+            .add_event::<SaveEvent>()
+            .add_event::<LoadEvent>()
+            .add_systems(
+                Last,
+                (
+                    save_game,
+                    load_game,
+                    save_on_exit.run_if(in_state(GameState::Playing)),
+                ),
+            );
+    }
+}
+
+/// Game state that can be written to and restored from the save file.
+///
+/// Gameplay modules implement this for their own state and register it
+/// with [`SaveAppExt::register_persistent`]. Each implementation is stored
+/// under its own [`Persistent::KEY`], so modules never need to know about
+/// each other.
+pub trait Persistent: Serialize + DeserializeOwned + Send + Sync + 'static {
+    /// Unique key for this state in the save file.
+    const KEY: &'static str;
+
+    /// Snapshot the current state, or `None` if there is nothing to save.
+    fn capture(world: &mut World) -> Option<Self>;
+
+    /// Restore a snapshot.
+    ///
+    /// Restoring happens before the levels are spawned, so state that lives
+    /// on level entities should be stashed and applied once they appear.
+    fn restore(self, world: &mut World);
+}
+
+pub trait SaveAppExt {
+    fn register_persistent<P: Persistent>(&mut self) -> &mut Self;
+}
+
+impl SaveAppExt for App {
+    fn register_persistent<P: Persistent>(&mut self) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<SaveRegistry>()
+            .0
+            .push(SaveEntry {
+                key: P::KEY,
+                capture: capture::<P>,
+                restore: restore::<P>,
+            });
+        self
+    }
+}
+
+fn capture<P: Persistent>(world: &mut World) -> Result<Option<String>> {
+    P::capture(world)
+        .map(|state| ron::to_string(&state))
+        .transpose()
+        .map_err(Into::into)
+}
+
+fn restore<P: Persistent>(world: &mut World, data: &str) -> Result {
+    let state: P = ron::from_str(data)?;
+    state.restore(world);
+    Ok(())
+}
+
+struct SaveEntry {
+    key: &'static str,
+    capture: fn(&mut World) -> Result<Option<String>>,
+    restore: fn(&mut World, &str) -> Result,
+}
+
+#[derive(Default, Resource)]
+struct SaveRegistry(Vec<SaveEntry>);
+
+/// The on-disk save format.
+///
+/// Each registered [`Persistent`] is serialized separately so that an
+/// entry that fails to restore doesn't take the rest of the save with it.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    pub entries: BTreeMap<String, String>,
+}
+
+/// Where the save file is written.
+#[derive(Resource)]
+pub struct SavePath(pub PathBuf);
+
+impl Default for SavePath {
+    fn default() -> Self {
+        Self(data_path("save.ron"))
+    }
+}
+
+impl SavePath {
+    pub fn exists(&self) -> bool {
+        self.0.exists()
+    }
+}
+
+/// Resolve a file in the game's data directory.
+///
+/// This defaults to `data/` in the working directory and can be
+/// overridden with `TIME_MARCHES_ON_DATA`.
+pub fn data_path(file: &str) -> PathBuf {
+    std::env::var_os("TIME_MARCHES_ON_DATA")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("data"))
+        .join(file)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_data(path: &std::path::Path, contents: &str) -> Result {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, contents)?;
+    Ok(())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn write_data(_: &std::path::Path, _: &str) -> Result {
+    Err("writing data is not supported on the web".into())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_data(path: &std::path::Path) -> Result<String> {
+    Ok(std::fs::read_to_string(path)?)
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn read_data(_: &std::path::Path) -> Result<String> {
+    Err("reading data is not supported on the web".into())
+}
+
+/// Write all registered [`Persistent`] state to the save file.
+#[derive(Debug, Default, Event)]
+pub struct SaveEvent;
+
+/// Restore all registered [`Persistent`] state from the save file and
+/// continue to [`GameState::Playing`].
+#[derive(Debug, Default, Event)]
+pub struct LoadEvent;
+
+pub(crate) fn save_game(mut reader: EventReader<SaveEvent>, mut commands: Commands) {
+    if reader.read().count() > 0 {
+        commands.queue(write_save);
+    }
+}
+
+fn save_on_exit(mut reader: EventReader<AppExit>, mut commands: Commands) {
+    if reader.read().count() > 0 {
+        commands.queue(write_save);
+    }
+}
+
+fn load_game(mut reader: EventReader<LoadEvent>, mut commands: Commands) {
+    if reader.read().count() > 0 {
+        commands.queue(read_save);
+    }
+}
+
+fn write_save(world: &mut World) -> Result {
+    let mut file = SaveFile {
+        version: SAVE_VERSION,
+        ..Default::default()
+    };
+
+    world.resource_scope(|world: &mut World, registry: Mut<SaveRegistry>| -> Result {
+        for entry in registry.0.iter() {
+            if let Some(data) = (entry.capture)(world)? {
+                file.entries.insert(entry.key.to_string(), data);
+            }
+        }
+
+        Ok(())
+    })?;
+
+    let contents = ron::ser::to_string_pretty(&file, ron::ser::PrettyConfig::default())?;
+    let path = &world.resource::<SavePath>().0;
+    write_data(path, &contents)?;
+    info!("saved game to {}", path.display());
+
+    Ok(())
+}
+
+fn read_save(world: &mut World) -> Result {
+    let path = &world.resource::<SavePath>().0;
+    let file: SaveFile = ron::from_str(&read_data(path)?)?;
+
+    if file.version != SAVE_VERSION {
+        return Err(format!(
+            "save version {} does not match the expected version {SAVE_VERSION}",
+            file.version
+        )
+        .into());
+    }
+
+    // Nothing from an earlier playthrough should survive into this one.
+    crate::reset::reset_world(world);
+
+    world.resource_scope(|world: &mut World, registry: Mut<SaveRegistry>| {
+        for entry in registry.0.iter() {
+            let Some(data) = file.entries.get(entry.key) else {
+                continue;
+            };
+
+            if let Err(e) = (entry.restore)(world, data) {
+                error!("failed to restore `{}`: {e}", entry.key);
+            }
+        }
+    });
+
+    world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Playing);
+
+    Ok(())
+}
@@ -10,16 +10,26 @@ use bevy_sequence::prelude::{FragmentEndEvent, FragmentEvent, IntoFragment};
 use core::marker::PhantomData;
 use std::time::Duration;
 
-use bevy::ecs::system::IntoSystem;
+use bevy::ecs::system::{IntoSystem, SystemId};
+
+use crate::reset::ResetAppExt;
 
 pub struct ObserverSequencePlugin;
 
 impl Plugin for ObserverSequencePlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<FragmentEvent<ObserverSequence>>()
+            .despawn_on_reset::<SequenceRoot>()
+            .despawn_on_reset::<DisabledObserver>()
+            .despawn_on_reset::<EnableObserver>()
+            .despawn_on_reset::<DelayTimer>()
+            .despawn_on_reset::<DelayedRun>()
             .add_systems(
                 Update,
-                (observer_sequence_runner, observer_sequence_delay).chain(),
+                (
+                    (observer_sequence_runner, observer_sequence_delay).chain(),
+                    run_delayed,
+                ),
             )
             .add_observer(disable_observer)
             .add_observer(enable_observer);
@@ -125,6 +135,68 @@ fn observer_sequence_delay(
     }
 }
 
+/// Run `system` once, after `duration`.
+///
+/// Unlike `bevy_sequence`'s `run_after`, a run that's still pending when
+/// the world resets is dropped, so nothing from the last playthrough fires
+/// in the menu or the next one.
+pub fn run_after<M>(
+    duration: Duration,
+    system: impl IntoSystem<(), (), M> + 'static,
+    commands: &mut Commands,
+) {
+    let system = commands.register_system(system);
+    commands.entity(system.entity()).insert(DelayedRun {
+        timer: Timer::new(duration, TimerMode::Once),
+        system,
+    });
+}
+
+/// Lives on the registered system's entity, so despawning it drops both.
+#[derive(Component)]
+struct DelayedRun {
+    timer: Timer,
+    system: SystemId,
+}
+
+fn run_delayed(mut runs: Query<&mut DelayedRun>, time: Res<Time>, mut commands: Commands) {
+    let delta = time.delta();
+
+    for mut run in &mut runs {
+        if run.timer.tick(delta).just_finished() {
+            commands.run_system(run.system);
+            commands.unregister_system(run.system);
+        }
+    }
+}
+
+/// Marks the root of a spawned sequence, so a reset can tear it down.
+#[derive(Component)]
+pub struct SequenceRoot;
+
+/// Mark `fragment` as a [`SequenceRoot`] when it's spawned.
+pub fn rooted<F>(fragment: F) -> Rooted<F> {
+    Rooted(fragment)
+}
+
+pub struct Rooted<F>(F);
+
+impl<D, C, F> IntoFragment<D, C> for Rooted<F>
+where
+    D: bevy_sequence::Threaded,
+    F: IntoFragment<D, C>,
+{
+    fn into_fragment(
+        self,
+        context: &bevy_sequence::prelude::Context<C>,
+        commands: &mut Commands,
+    ) -> bevy_sequence::prelude::FragmentId {
+        let id = self.0.into_fragment(context, commands);
+        commands.entity(id.entity()).insert(SequenceRoot);
+        id
+    }
+}
+
 pub struct Delay {
     delay: Duration,
 }
@@ -326,3 +398,60 @@ where
 //         bevy_sequence::prelude::FragmentId::new(id)
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestApp;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[derive(Default, Resource)]
+    struct Runs(usize);
+
+    fn schedule_run(app: &mut TestApp) {
+        app.init_resource::<Runs>();
+        app.world_mut()
+            .run_system_once(|mut commands: Commands| {
+                run_after(
+                    Duration::from_secs(1),
+                    |mut runs: ResMut<Runs>| runs.0 += 1,
+                    &mut commands,
+                );
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn runs_once_after_the_delay() {
+        let mut app = TestApp::new(());
+        schedule_run(&mut app);
+
+        app.advance(0.5);
+        assert_eq!(app.world().resource::<Runs>().0, 0);
+
+        app.advance(1.0);
+        assert_eq!(app.world().resource::<Runs>().0, 1);
+
+        app.advance(2.0);
+        assert_eq!(app.world().resource::<Runs>().0, 1);
+        assert!(
+            app.world_mut()
+                .query::<&DelayedRun>()
+                .iter(app.world())
+                .next()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn reset_drops_pending_runs() {
+        let mut app = TestApp::new(());
+        schedule_run(&mut app);
+
+        app.advance(0.5);
+        app.reset();
+        app.advance(2.0);
+
+        assert_eq!(app.world().resource::<Runs>().0, 0);
+    }
+}
@@ -0,0 +1,367 @@
+use bevy::{
+    input_focus::{InputFocus, directional_navigation::DirectionalNavigationMap},
+    math::CompassOctant,
+    prelude::*,
+};
+use bevy_seedling::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::audio::{GlyphBus, MusicBus, SfxBus};
+use crate::cli::LaunchOptions;
+use crate::controls::OpenControls;
+use crate::save::{data_path, read_data, write_data};
+use crate::widgets;
+use crate::{GameState, PlayingState};
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Settings::load())
+            .add_systems(
+                Update,
+                (
+                    apply_volumes.run_if(resource_changed::<Settings>),
+                    show_values,
+                ),
+            )
+            .add_systems(OnExit(GameState::Menu), close_on_exit)
+            .add_systems(OnExit(PlayingState::Paused), close_on_exit)
+            .add_observer(open_settings)
+            .add_observer(close_settings)
+            .add_observer(click_button);
+    }
+}
+
+/// The settings file, in the game's data directory.
+const SETTINGS_FILE: &str = "settings.ron";
+
+/// How much each button press changes a volume.
+const VOLUME_STEP: f32 = 0.1;
+
+/// Player preferences that persist between launches.
+///
+/// Volumes are linear, from silent at `0.0` to unchanged at `1.0`.
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+    pub glyphs: f32,
+    pub muted: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            music: 1.0,
+            sfx: 1.0,
+            glyphs: 1.0,
+            muted: false,
+        }
+    }
+}
+
+impl Settings {
+    fn load() -> Self {
+        let path = data_path(SETTINGS_FILE);
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match read_data(&path).and_then(|data| Ok(ron::from_str(&data)?)) {
+            Ok(settings) => settings,
+            Err(e) => {
+                error!("failed to read settings from {}: {e}", path.display());
+                Self::default()
+            }
+        }
+    }
+
+    fn save(&self) -> Result {
+        let path = data_path(SETTINGS_FILE);
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        write_data(&path, &contents)?;
+        info!("saved settings to {}", path.display());
+
+        Ok(())
+    }
+
+    fn volume(&self, volume: VolumeSetting) -> f32 {
+        match volume {
+            VolumeSetting::Master => self.master,
+            VolumeSetting::Music => self.music,
+            VolumeSetting::Sfx => self.sfx,
+            VolumeSetting::Glyphs => self.glyphs,
+        }
+    }
+
+    fn volume_mut(&mut self, volume: VolumeSetting) -> &mut f32 {
+        match volume {
+            VolumeSetting::Master => &mut self.master,
+            VolumeSetting::Music => &mut self.music,
+            VolumeSetting::Sfx => &mut self.sfx,
+            VolumeSetting::Glyphs => &mut self.glyphs,
+        }
+    }
+}
+
+fn apply_volumes(
+    settings: Res<Settings>,
+    options: Option<Res<LaunchOptions>>,
+    mut main: Query<&mut VolumeNode, With<MainBus>>,
+    mut music: Query<&mut VolumeNode, (With<MusicBus>, Without<MainBus>)>,
+    mut sfx: Query<&mut VolumeNode, (With<SfxBus>, Without<MainBus>, Without<MusicBus>)>,
+    mut glyphs: Query<
+        &mut VolumeNode,
+        (
+            With<GlyphBus>,
+            Without<MainBus>,
+            Without<MusicBus>,
+            Without<SfxBus>,
+        ),
+    >,
+) {
+    // `--mute` silences the session without touching the saved settings.
+    let muted = settings.muted || options.is_some_and(|options| options.mute);
+    let master = if muted { 0.0 } else { settings.master };
+
+    for mut node in main.iter_mut() {
+        node.volume = Volume::Linear(master);
+    }
+    for mut node in music.iter_mut() {
+        node.volume = Volume::Linear(settings.music);
+    }
+    for mut node in sfx.iter_mut() {
+        node.volume = Volume::Linear(settings.sfx);
+    }
+    for mut node in glyphs.iter_mut() {
+        node.volume = Volume::Linear(settings.glyphs);
+    }
+}
+
+/// Open the settings screen over whatever is on screen.
+#[derive(Debug, Default, Event)]
+pub struct OpenSettings;
+
+/// Close the settings screen, saving any changes.
+#[derive(Debug, Default, Event)]
+pub struct CloseSettings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VolumeSetting {
+    Master,
+    Music,
+    Sfx,
+    Glyphs,
+}
+
+impl VolumeSetting {
+    const ALL: [Self; 4] = [Self::Master, Self::Music, Self::Sfx, Self::Glyphs];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Master => "Master",
+            Self::Music => "Music",
+            Self::Sfx => "Effects",
+            Self::Glyphs => "Dialogue",
+        }
+    }
+}
+
+/// The open settings screen.
+#[derive(Component)]
+struct SettingsPanel;
+
+#[derive(Component)]
+enum SettingsButton {
+    Adjust(VolumeSetting, f32),
+    ToggleMute,
+    Controls,
+    Back,
+}
+
+/// Text showing the current value of a volume, or the mute toggle if `None`.
+#[derive(Component)]
+struct SettingValue(Option<VolumeSetting>);
+
+fn open_settings(
+    _: Trigger<OpenSettings>,
+    existing: Query<(), With<SettingsPanel>>,
+    mut directional_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    mut commands: Commands,
+    server: Res<AssetServer>,
+) {
+    if !existing.is_empty() {
+        return;
+    }
+
+    let font = server.load("fonts/raster-forge.ttf");
+    let panel = commands
+        .spawn((
+            SettingsPanel,
+            widgets::overlay(&input_focus, 10, Color::BLACK.with_alpha(0.9), 15.0),
+            children![label("Settings", &font)],
+        ))
+        .id();
+
+    let mut decrease = Vec::new();
+    let mut increase = Vec::new();
+    for volume in VolumeSetting::ALL {
+        let down = commands
+            .spawn(button(
+                "<",
+                SettingsButton::Adjust(volume, -VOLUME_STEP),
+                &font,
+            ))
+            .id();
+        let up = commands
+            .spawn(button(
+                ">",
+                SettingsButton::Adjust(volume, VOLUME_STEP),
+                &font,
+            ))
+            .id();
+
+        let name = commands.spawn(label(volume.label(), &font)).id();
+        let value = commands
+            .spawn((SettingValue(Some(volume)), label("", &font)))
+            .id();
+        commands
+            .spawn((row(), ChildOf(panel)))
+            .add_children(&[name, down, value, up]);
+
+        directional_nav_map.add_looping_edges(&[down, up], CompassOctant::East);
+        decrease.push(down);
+        increase.push(up);
+    }
+
+    let mute = commands
+        .spawn(button("Mute", SettingsButton::ToggleMute, &font))
+        .id();
+    let value = commands.spawn((SettingValue(None), label("", &font))).id();
+    commands
+        .spawn((row(), ChildOf(panel)))
+        .add_children(&[mute, value]);
+
+    let controls = commands
+        .spawn((
+            button("Controls", SettingsButton::Controls, &font),
+            ChildOf(panel),
+        ))
+        .id();
+
+    let back = commands
+        .spawn((button("Back", SettingsButton::Back, &font), ChildOf(panel)))
+        .id();
+
+    decrease.extend([mute, controls, back]);
+    increase.extend([mute, controls, back]);
+    directional_nav_map.add_edges(&decrease, CompassOctant::South);
+    directional_nav_map.add_edges(&increase, CompassOctant::South);
+
+    input_focus.set(decrease[0]);
+}
+
+fn label(text: &str, font: &Handle<Font>) -> impl Bundle {
+    (
+        Text::new(text),
+        TextFont {
+            font: font.clone(),
+            ..Default::default()
+        },
+        Node {
+            width: Val::Px(200.0),
+            ..default()
+        },
+    )
+}
+
+fn row() -> impl Bundle {
+    Node {
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(25.0),
+        ..default()
+    }
+}
+
+fn button(text: &'static str, action: SettingsButton, font: &Handle<Font>) -> impl Bundle {
+    (
+        action,
+        widgets::button(
+            text,
+            20.0,
+            Node {
+                width: Val::Px(120.0),
+                height: Val::Px(50.0),
+                ..default()
+            },
+            font,
+        ),
+    )
+}
+
+fn show_values(
+    settings: Res<Settings>,
+    added: Query<(), Added<SettingValue>>,
+    mut values: Query<(&SettingValue, &mut Text)>,
+) {
+    if !settings.is_changed() && added.is_empty() {
+        return;
+    }
+
+    for (value, mut text) in values.iter_mut() {
+        text.0 = match value.0 {
+            Some(volume) => format!("{}%", (settings.volume(volume) * 100.0).round()),
+            None if settings.muted => "On".into(),
+            None => "Off".into(),
+        };
+    }
+}
+
+fn click_button(
+    trigger: Trigger<Pointer<Click>>,
+    buttons: Query<&SettingsButton>,
+    mut settings: ResMut<Settings>,
+    mut commands: Commands,
+) {
+    let Ok(button) = buttons.get(trigger.target()) else {
+        return;
+    };
+
+    match button {
+        SettingsButton::Adjust(volume, step) => {
+            let value = settings.volume_mut(*volume);
+            *value = (*value + step).clamp(0.0, 1.0);
+        }
+        SettingsButton::ToggleMute => {
+            settings.muted = !settings.muted;
+        }
+        SettingsButton::Controls => {
+            commands.trigger(OpenControls);
+        }
+        SettingsButton::Back => {
+            commands.trigger(CloseSettings);
+        }
+    }
+}
+
+fn close_settings(
+    _: Trigger<CloseSettings>,
+    panel: Single<Entity, With<SettingsPanel>>,
+    settings: Res<Settings>,
+    mut commands: Commands,
+) -> Result {
+    commands.run_system_cached_with(widgets::close_overlay, *panel);
+    settings.save()
+}
+
+/// Don't leave the screen open over a menu that's going away.
+fn close_on_exit(panel: Query<(), With<SettingsPanel>>, mut commands: Commands) {
+    if !panel.is_empty() {
+        commands.trigger(CloseSettings);
+    }
+}
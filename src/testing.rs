@@ -0,0 +1,244 @@
+//! A headless harness for gameplay tests.
+//!
+//! [`TestApp`] builds the gameplay plugins on [`MinimalPlugins`], with no
+//! window, renderer, or audio output. Played samples are recorded in the
+//! [`AudioLog`]. Input is injected by pressing the keys the
+//! `bevy_enhanced_input` actions are bound to, and virtual time advances by
+//! a fixed step each frame so timers and sequences are deterministic.
+//!
+//! ```ignore
+//! let mut app = TestApp::new(PillsPlugin);
+//! let player = app.spawn_player(Vec2::ZERO);
+//! let pills = app.world_mut().spawn(world::Pills { flavor: "Pills.".into() }).id();
+//!
+//! app.touch(pills).interact().finish_dialogue();
+//! assert!(app.has_item("Pills"));
+//! ```
+
+use std::time::Duration;
+
+use avian2d::prelude::CollidingEntities;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use bevy::text::TextPlugin;
+use bevy::time::TimeUpdateStrategy;
+
+use crate::audio::null::AudioLog;
+use crate::flags::StoryFlags;
+use crate::inventory::item::InventoryItem;
+use crate::player::{Player, PlayerCollider};
+use crate::reset::ResetWorld;
+use crate::{GameState, PlayingState, textbox};
+
+/// The virtual time that passes each frame.
+pub const FRAME: Duration = Duration::from_nanos(16_666_667);
+
+/// How many times [`TestApp::finish_dialogue`] interacts before giving up.
+const DIALOGUE_INTERACTION_LIMIT: usize = 100;
+
+/// A gameplay app that runs without a window, renderer, or audio device.
+#[derive(Deref, DerefMut)]
+pub struct TestApp(App);
+
+impl TestApp {
+    /// Build the core gameplay plugins along with the `plugins` under test,
+    /// starting in [`GameState::Playing`].
+    pub fn new<M>(plugins: impl Plugins<M>) -> Self {
+        let mut app = App::new();
+
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            ImagePlugin::default(),
+            TransformPlugin,
+            StatesPlugin,
+            InputPlugin,
+            TextPlugin,
+            bevy_enhanced_input::EnhancedInputPlugin,
+            bevy_pretty_text::PrettyTextPlugin,
+            bevy_sequence::SequencePlugin,
+            bevy_tween::DefaultTweenPlugins,
+        ))
+        .add_plugins((
+            crate::audio::null::NullAudioPlugin,
+            crate::audio::AudioPlugin,
+            crate::save::SavePlugin,
+            crate::reset::ResetPlugin,
+            crate::flags::FlagsPlugin,
+            crate::animation::AnimationPlugin,
+            crate::player::PlayerPlugin,
+            crate::textbox::TextboxPlugin,
+            crate::backlog::BacklogPlugin,
+            crate::interactions::InteractionPlugin,
+            crate::inventory::item::ItemsPlugin,
+            crate::inventory::item_use::ItemUsePlugin,
+            crate::cutscene::CutscenePlugin,
+            crate::notes::NotesPlugin,
+            crate::sequence::ObserverSequencePlugin,
+        ))
+        .add_plugins(plugins)
+        .init_resource::<crate::controls::Controls>()
+        .init_resource::<crate::GameRng>()
+        .init_asset::<TextureAtlasLayout>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+        .insert_state(GameState::Playing)
+        .add_sub_state::<PlayingState>();
+
+        app.finish();
+        app.cleanup();
+        app.update();
+
+        Self(app)
+    }
+
+    /// Spawn the player at `position`, returning the player entity.
+    pub fn spawn_player(&mut self, position: Vec2) -> Entity {
+        let player = self
+            .world_mut()
+            .spawn((Player, Transform::from_translation(position.extend(0.))))
+            .id();
+        self.update();
+        player
+    }
+
+    /// Reset the world as New Game would, then run an update.
+    pub fn reset(&mut self) -> &mut Self {
+        self.world_mut().send_event(ResetWorld);
+        self.update();
+        self
+    }
+
+    /// Run `frames` updates.
+    pub fn step(&mut self, frames: usize) -> &mut Self {
+        for _ in 0..frames {
+            self.update();
+        }
+        self
+    }
+
+    /// Run updates until at least `seconds` of virtual time have passed.
+    pub fn advance(&mut self, seconds: f32) -> &mut Self {
+        let frames = (seconds / FRAME.as_secs_f32()).ceil() as usize;
+        self.step(frames)
+    }
+
+    /// Press `key` for a single frame, then release it.
+    pub fn tap(&mut self, key: KeyCode) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+        self.update();
+        self.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(key);
+        self.update();
+        self
+    }
+
+    /// Hold `key` for `seconds`, then release it.
+    pub fn hold(&mut self, key: KeyCode, seconds: f32) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+        self.advance(seconds);
+        self.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(key);
+        self.update();
+        self
+    }
+
+    /// Fire `InteractAction`, or `Interact` while a textbox is open.
+    pub fn interact(&mut self) -> &mut Self {
+        self.tap(KeyCode::KeyJ)
+    }
+
+    /// Fire `MoveAction` towards `direction` for `seconds`.
+    pub fn walk(&mut self, direction: Dir2, seconds: f32) -> &mut Self {
+        let keys = [
+            (direction.x > 0.5, KeyCode::KeyD),
+            (direction.x < -0.5, KeyCode::KeyA),
+            (direction.y > 0.5, KeyCode::KeyW),
+            (direction.y < -0.5, KeyCode::KeyS),
+        ];
+
+        let mut input = self.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        for (_, key) in keys.iter().filter(|(held, _)| *held) {
+            input.press(*key);
+        }
+
+        self.advance(seconds);
+
+        let mut input = self.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        for (_, key) in keys {
+            input.release(key);
+        }
+        self.update();
+        self
+    }
+
+    /// Put the player within reach of the interactable `target`.
+    ///
+    /// There's no physics step in tests, so this fills in the
+    /// [`CollidingEntities`] that avian would otherwise compute.
+    pub fn touch(&mut self, target: Entity) -> &mut Self {
+        let collider = self
+            .world_mut()
+            .query_filtered::<Entity, With<PlayerCollider>>()
+            .single(self.world())
+            .expect("`touch` requires a spawned player");
+
+        let world = self.world_mut();
+        for mut colliding in world.query::<&mut CollidingEntities>().iter_mut(world) {
+            colliding.remove(&collider);
+        }
+
+        self.world_mut()
+            .entity_mut(target)
+            .get_mut::<CollidingEntities>()
+            .expect("`touch` target should be `Interactable`")
+            .insert(collider);
+        self
+    }
+
+    /// Advance through any open textboxes until they close.
+    pub fn finish_dialogue(&mut self) -> &mut Self {
+        for _ in 0..DIALOGUE_INTERACTION_LIMIT {
+            if !self.textbox_open() {
+                return self;
+            }
+
+            self.interact();
+        }
+
+        panic!("textbox still open after {DIALOGUE_INTERACTION_LIMIT} interactions");
+    }
+
+    pub fn textbox_open(&mut self) -> bool {
+        self.world_mut()
+            .query_filtered::<(), With<textbox::Textbox>>()
+            .iter(self.world())
+            .next()
+            .is_some()
+    }
+
+    pub fn audio(&self) -> &AudioLog {
+        self.world().resource::<AudioLog>()
+    }
+
+    pub fn audio_mut(&mut self) -> Mut<'_, AudioLog> {
+        self.world_mut().resource_mut::<AudioLog>()
+    }
+
+    pub fn flags(&self) -> &StoryFlags {
+        self.world().resource::<StoryFlags>()
+    }
+
+    pub fn has_item(&mut self, name: &str) -> bool {
+        self.world_mut()
+            .query::<&InventoryItem>()
+            .iter(self.world())
+            .any(|item| item.name == name)
+    }
+}
@@ -0,0 +1,319 @@
+//! Inline markup for dialogue lines.
+//!
+//! ```text
+//! She's[pause=0.8] [speed=8][shake]not breathing.[/shake][/speed]
+//! The [color=red]red[/color] door, [wave]humming[/wave].
+//! ```
+//!
+//! Tags that aren't recognized, or have an invalid value like a negative
+//! pause, are left in the text as written. Sentence and clause punctuation
+//! pauses the typewriter on its own.
+
+use std::ops::Range;
+
+use bevy::prelude::*;
+
+/// How long the typewriter pauses after a sentence ends.
+const SENTENCE_PAUSE: f32 = 0.35;
+
+/// How long the typewriter pauses after a comma, colon or semicolon.
+const CLAUSE_PAUSE: f32 = 0.12;
+
+/// How a span of glyphs moves once revealed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    Shake,
+    Wave,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Style {
+    /// Characters per second, in place of the textbox's usual speed.
+    pub speed: Option<f32>,
+    pub effect: Option<Effect>,
+    pub color: Option<Color>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glyph {
+    pub char: char,
+    pub style: Style,
+    /// Seconds to wait before this glyph, on top of its usual delay.
+    pub pause: f32,
+}
+
+/// A dialogue line with its markup parsed out.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Markup {
+    pub glyphs: Vec<Glyph>,
+    /// Seconds to hold after the last glyph before the line is finished.
+    pub trailing_pause: f32,
+}
+
+enum Tag {
+    Pause(f32),
+    Speed(f32),
+    EndSpeed,
+    Effect(Effect),
+    EndEffect(Effect),
+    Color(Color),
+    EndColor,
+}
+
+impl Tag {
+    fn parse(tag: &str) -> Option<Self> {
+        let tag = match tag.split_once('=') {
+            Some(("pause", secs)) => Self::Pause(
+                secs.parse::<f32>()
+                    .ok()
+                    .filter(|secs| secs.is_finite() && *secs >= 0.)?,
+            ),
+            Some(("speed", cps)) => Self::Speed(
+                cps.parse::<f32>()
+                    .ok()
+                    .filter(|cps| cps.is_finite() && *cps > 0.)?,
+            ),
+            Some(("color", color)) => Self::Color(parse_color(color)?),
+            Some(_) => return None,
+            None => match tag {
+                "/speed" => Self::EndSpeed,
+                "shake" => Self::Effect(Effect::Shake),
+                "/shake" => Self::EndEffect(Effect::Shake),
+                "wave" => Self::Effect(Effect::Wave),
+                "/wave" => Self::EndEffect(Effect::Wave),
+                "/color" => Self::EndColor,
+                _ => return None,
+            },
+        };
+
+        Some(tag)
+    }
+}
+
+fn parse_color(color: &str) -> Option<Color> {
+    let color = match color {
+        "red" => Color::srgb(0.8, 0.1, 0.1),
+        "yellow" => Color::srgb(0.9, 0.8, 0.3),
+        "blue" => Color::srgb(0.4, 0.5, 0.9),
+        "grey" | "gray" => Color::srgb(0.6, 0.6, 0.6),
+        "white" => Color::WHITE,
+        hex => Srgba::hex(hex).ok()?.into(),
+    };
+
+    Some(color)
+}
+
+impl Markup {
+    pub fn parse(line: &str) -> Self {
+        let mut glyphs = Vec::with_capacity(line.len());
+        let mut speeds = Vec::new();
+        let mut effects = Vec::new();
+        let mut colors = Vec::new();
+        let mut pause = 0.;
+
+        let mut rest = line;
+        while let Some(char) = rest.chars().next() {
+            let tag = (char == '[')
+                .then(|| rest.find(']'))
+                .flatten()
+                .and_then(|end| Some((Tag::parse(&rest[1..end])?, end)));
+
+            if let Some((tag, end)) = tag {
+                match tag {
+                    Tag::Pause(secs) => pause += secs,
+                    Tag::Speed(cps) => speeds.push(cps),
+                    Tag::EndSpeed => {
+                        speeds.pop();
+                    }
+                    Tag::Effect(effect) => effects.push(effect),
+                    Tag::EndEffect(effect) => {
+                        if let Some(index) = effects.iter().rposition(|open| *open == effect) {
+                            effects.remove(index);
+                        }
+                    }
+                    Tag::Color(color) => colors.push(color),
+                    Tag::EndColor => {
+                        colors.pop();
+                    }
+                }
+
+                rest = &rest[end + 1..];
+                continue;
+            }
+
+            glyphs.push(Glyph {
+                char,
+                style: Style {
+                    speed: speeds.last().copied(),
+                    effect: effects.last().copied(),
+                    color: colors.last().copied(),
+                },
+                pause: std::mem::take(&mut pause),
+            });
+            rest = &rest[char.len_utf8()..];
+        }
+
+        // Punctuation only pauses when more of the line follows it.
+        for i in 1..glyphs.len() {
+            if glyphs[i].char.is_whitespace() {
+                glyphs[i].pause += match glyphs[i - 1].char {
+                    '.' | '!' | '?' => SENTENCE_PAUSE,
+                    ',' | ';' | ':' => CLAUSE_PAUSE,
+                    _ => 0.,
+                };
+            }
+        }
+
+        Self {
+            glyphs,
+            trailing_pause: pause,
+        }
+    }
+
+    /// The line without its markup, as shown in the backlog.
+    pub fn plain(&self) -> String {
+        self.glyphs.iter().map(|glyph| glyph.char).collect()
+    }
+
+    /// Runs of consecutive glyphs that share a style.
+    pub fn spans(&self) -> Vec<(Style, Range<usize>)> {
+        let mut spans = Vec::<(Style, Range<usize>)>::new();
+        for (i, glyph) in self.glyphs.iter().enumerate() {
+            match spans.last_mut() {
+                Some((style, range)) if *style == glyph.style => range.end = i + 1,
+                _ => spans.push((glyph.style, i..i + 1)),
+            }
+        }
+
+        spans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(markup: &Markup) -> Vec<(char, Style)> {
+        markup
+            .glyphs
+            .iter()
+            .map(|glyph| (glyph.char, glyph.style))
+            .collect()
+    }
+
+    #[test]
+    fn nested_tags_apply_innermost_first() {
+        let markup = Markup::parse("[speed=10][shake]a[wave]b[/wave]c[/shake]d[/speed]e");
+
+        let shake = Style {
+            speed: Some(10.),
+            effect: Some(Effect::Shake),
+            color: None,
+        };
+        assert_eq!(
+            chars(&markup),
+            vec![
+                ('a', shake),
+                (
+                    'b',
+                    Style {
+                        effect: Some(Effect::Wave),
+                        ..shake
+                    }
+                ),
+                ('c', shake),
+                (
+                    'd',
+                    Style {
+                        speed: Some(10.),
+                        ..Default::default()
+                    }
+                ),
+                ('e', Style::default()),
+            ]
+        );
+    }
+
+    #[test]
+    fn closing_an_outer_effect_keeps_the_inner_one() {
+        let markup = Markup::parse("[wave][shake]a[/wave]b[/shake]");
+
+        assert_eq!(markup.glyphs[1].style.effect, Some(Effect::Shake));
+    }
+
+    #[test]
+    fn unknown_and_invalid_tags_are_left_in_the_text() {
+        for line in [
+            "[bold]hi[/bold]",
+            "[pause=-1]hi",
+            "[pause=soon]hi",
+            "[speed=0]hi",
+            "[color=notacolor]hi",
+            "[unclosed hi",
+        ] {
+            let markup = Markup::parse(line);
+
+            assert_eq!(markup.plain(), line);
+            assert!(markup.glyphs.iter().all(|glyph| glyph.pause == 0.));
+        }
+    }
+
+    #[test]
+    fn pauses_attach_to_the_next_glyph() {
+        let markup = Markup::parse("a[pause=0.5][pause=0.25]b");
+
+        assert_eq!(markup.plain(), "ab");
+        assert_eq!(markup.glyphs[0].pause, 0.);
+        assert_eq!(markup.glyphs[1].pause, 0.75);
+        assert_eq!(markup.trailing_pause, 0.);
+    }
+
+    #[test]
+    fn trailing_pauses_are_kept() {
+        let markup = Markup::parse("wait for it[pause=1.5]");
+
+        assert_eq!(markup.plain(), "wait for it");
+        assert_eq!(markup.trailing_pause, 1.5);
+    }
+
+    #[test]
+    fn punctuation_pauses_before_the_next_word() {
+        let markup = Markup::parse("Wait, no. Stop");
+
+        let pauses = markup
+            .glyphs
+            .iter()
+            .filter(|glyph| glyph.pause > 0.)
+            .map(|glyph| glyph.pause)
+            .collect::<Vec<_>>();
+        assert_eq!(pauses, vec![CLAUSE_PAUSE, SENTENCE_PAUSE]);
+    }
+
+    #[test]
+    fn punctuation_ending_the_line_does_not_pause() {
+        let markup = Markup::parse("Stop.");
+
+        assert!(markup.glyphs.iter().all(|glyph| glyph.pause == 0.));
+        assert_eq!(markup.trailing_pause, 0.);
+    }
+
+    #[test]
+    fn colors_by_name_and_hex() {
+        let markup = Markup::parse("[color=red]a[color=#00ff00]b[/color]c[/color]d");
+
+        let colors = markup
+            .glyphs
+            .iter()
+            .map(|glyph| glyph.style.color)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            colors,
+            vec![
+                Some(Color::srgb(0.8, 0.1, 0.1)),
+                Some(Srgba::hex("#00ff00").unwrap().into()),
+                Some(Color::srgb(0.8, 0.1, 0.1)),
+                None,
+            ]
+        );
+    }
+}
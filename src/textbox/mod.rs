@@ -0,0 +1,1114 @@
+use std::borrow::Cow;
+use std::ops::Range;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::text::TextBounds;
+use bevy_enhanced_input::prelude::*;
+use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
+use bevy_pretty_text::prelude::GlyphRevealed;
+use bevy_seedling::prelude::Volume;
+use bevy_seedling::sample::{PitchRange, SamplePlayer};
+
+use crate::animation::{AnimationAppExt, AnimationSprite};
+use crate::audio::GlyphPool;
+use crate::backlog::DialogueHistory;
+use crate::controls::{Control, Controls};
+use crate::cutscene::chara::Chara;
+use crate::cutscene::skip::CutscenePlayback;
+use crate::player::{InhibitAddEvent, InhibitRemoveEvent, Player, PlayerContext};
+use crate::reset::ResetAppExt;
+use markup::Markup;
+use typewriter::{Typewriter, TypewriterFinished};
+
+pub mod markup;
+pub mod typewriter;
+
+pub struct TextboxPlugin;
+
+impl Plugin for TextboxPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(typewriter::TypewriterPlugin)
+            .add_input_context::<TextboxContext>()
+            .register_layout(
+                "textures/textbox-await.png",
+                TextureAtlasLayout::from_grid(
+                    UVec2::new(crate::WIDTH as u32, crate::HEIGHT as u32),
+                    4,
+                    1,
+                    None,
+                    None,
+                ),
+            )
+            .init_resource::<TextboxSections>()
+            .reset_resource::<TextboxSections>()
+            .despawn_on_reset::<Textbox>()
+            .add_event::<TextboxEvent>()
+            .add_event::<TextboxFinished>()
+            .add_event::<CharacterEvent>()
+            .add_event::<ExpressionEvent>()
+            .add_event::<TextboxCloseInteraction>()
+            .add_event::<TextboxCloseEvent>()
+            .add_event::<ChoiceSelected>()
+            .init_resource::<GlyphReveal>()
+            .reset_resource::<GlyphReveal>()
+            .add_systems(
+                Update,
+                (
+                    (
+                        close_textbox,
+                        textbox_event,
+                        handle_sprite_event,
+                        show_portrait,
+                    )
+                        .chain(),
+                    auto_advance,
+                    fade_portraits,
+                    highlight_choices,
+                ),
+            )
+            .add_observer(bind)
+            .add_observer(fade_new_sprite)
+            .add_observer(textbox_input)
+            .add_observer(reveal_textbox)
+            .add_observer(navigate_choices);
+
+        for chara in Chara::ALL {
+            for (_, portrait) in chara.portraits() {
+                if let CharacterSprite::Animated { path, frames, .. } = portrait {
+                    app.register_layout(
+                        path,
+                        TextureAtlasLayout::from_grid(
+                            UVec2::new(crate::WIDTH as u32, crate::HEIGHT as u32),
+                            frames.end as u32,
+                            1,
+                            None,
+                            None,
+                        ),
+                    );
+                }
+            }
+        }
+    }
+}
+
+pub fn glyph_sample(glyph: &'static str) -> String {
+    format!("audio/sfx/glyph/{}", glyph)
+}
+
+/// Spawn a textbox and present each `TextBlurb` in sequence with breaks.
+///
+/// Events that arrive while the textbox is busy are queued by
+/// [`TextboxPriority`], and a [`TextboxFinished`] is sent once the player
+/// dismisses the last blurb.
+#[derive(Event)]
+pub struct TextboxEvent {
+    id: TextboxId,
+    blurbs: Vec<TextBlurb>,
+    despawn_when_finished: bool,
+    priority: TextboxPriority,
+    auto: Option<AutoAdvance>,
+}
+
+#[allow(unused)]
+impl TextboxEvent {
+    pub fn new(sections: impl IntoIterator<Item = TextBlurb>) -> Self {
+        let sections = sections.into_iter().collect::<Vec<_>>();
+        debug_assert!(!sections.is_empty());
+
+        Self {
+            id: TextboxId::next(),
+            blurbs: sections,
+            despawn_when_finished: true,
+            priority: TextboxPriority::default(),
+            auto: None,
+        }
+    }
+
+    pub fn section(section: TextBlurb) -> Self {
+        Self::new([section])
+    }
+
+    /// A cutscene line, which leaves the textbox open for the next line
+    /// until a [`TextboxCloseEvent`].
+    pub fn section_retained(section: TextBlurb) -> Self {
+        Self {
+            despawn_when_finished: false,
+            priority: TextboxPriority::Cutscene,
+            ..Self::section(section)
+        }
+    }
+
+    pub fn with_priority(mut self, priority: TextboxPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_auto_advance(mut self, auto: AutoAdvance) -> Self {
+        self.auto = Some(auto);
+        self
+    }
+
+    /// Identifies this event's [`TextboxFinished`].
+    pub fn id(&self) -> TextboxId {
+        self.id
+    }
+}
+
+/// Advance past each blurb on its own once it's been shown for `hold`,
+/// rather than waiting for the player.
+#[derive(Debug, Clone, Copy)]
+pub struct AutoAdvance {
+    pub hold: Duration,
+    /// Whether to leave out the indicator that the textbox is waiting.
+    pub hide_indicator: bool,
+    /// Whether `Interact` is ignored, so the text can't be hurried along.
+    pub ignore_input: bool,
+}
+
+impl AutoAdvance {
+    /// Hold for `secs`, without the indicator or input.
+    pub fn new(secs: f32) -> Self {
+        Self {
+            hold: Duration::from_secs_f32(secs),
+            hide_indicator: true,
+            ignore_input: true,
+        }
+    }
+}
+
+/// How a [`TextboxEvent`] competes for the textbox.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TextboxPriority {
+    /// Incidental lines, like inspecting an object.
+    ///
+    /// These wait their turn, and are merged into a queued or showing
+    /// request with the same text.
+    Flavor,
+    /// Lines the story depends on, like picking up an item.
+    #[default]
+    Story,
+    /// Cutscene dialogue, which replaces showing flavor text and goes ahead
+    /// of everything else that's waiting.
+    Cutscene,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextboxId(u64);
+
+impl TextboxId {
+    fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Sent once a [`TextboxEvent`] is done with the textbox.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct TextboxFinished {
+    pub id: TextboxId,
+    /// Whether the text was cut off, by a cutscene or the textbox closing,
+    /// before the player dismissed it.
+    pub interrupted: bool,
+}
+
+/// Sets the dialog sprite.
+#[derive(Event)]
+pub struct CharacterEvent {
+    pub speaker: Speaker,
+    pub sprite: Option<CharacterSprite>,
+    pub glyph: Arc<dyn Fn(&mut Commands, &AssetServer) + Send + Sync>,
+}
+
+/// Shows the [`CharacterEvent`]'s character with one of their other
+/// [portraits](Chara::portrait).
+#[derive(Event)]
+pub struct ExpressionEvent(pub String);
+
+#[derive(Clone)]
+pub struct TextBlurb {
+    text: Cow<'static, str>,
+    speaker: Speaker,
+    character: Option<CharacterSprite>,
+    glyph: Arc<dyn Fn(&mut Commands, &AssetServer) + Send + Sync>,
+    choices: Vec<String>,
+}
+
+impl TextBlurb {
+    /// New text blurb with relative asset path:
+    ///   - character: `textures/characters/`
+    pub fn new(
+        text: impl Into<Cow<'static, str>>,
+        character: Option<&str>,
+        glyph: impl Fn(&mut Commands, &AssetServer) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            text: text.into(),
+            speaker: Speaker::Narrator,
+            character: character.map(CharacterSprite::new),
+            glyph: Arc::new(glyph),
+            choices: Vec::new(),
+        }
+    }
+
+    /// Present a list of options under the text once it's revealed.
+    ///
+    /// The player's pick is sent as a [`ChoiceSelected`] event.
+    pub fn with_choices(mut self, choices: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.choices = choices.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn narrator(text: impl Into<Cow<'static, str>>) -> Self {
+        // lol
+        Self::new(text, None, |commands, server| {
+            commands.spawn((
+                PitchRange::new(0.02),
+                GlyphPool,
+                SamplePlayer {
+                    sample: server.load(glyph_sample("low.wav")),
+                    volume: Volume::Linear(0.5),
+                    ..Default::default()
+                },
+            ));
+        })
+    }
+
+    pub fn main_character(text: impl Into<Cow<'static, str>>) -> Self {
+        Self::new(text, Some("main.png"), |commands, server| {
+            commands.spawn((
+                PitchRange::new(0.05),
+                GlyphPool,
+                SamplePlayer {
+                    sample: server.load(glyph_sample("medium.wav")),
+                    volume: Volume::Linear(0.5),
+                    ..Default::default()
+                },
+            ));
+        })
+        .with_speaker(Speaker::MainCharacter)
+    }
+
+    /// Attribute this blurb to `speaker` in the dialogue history.
+    pub fn with_speaker(mut self, speaker: Speaker) -> Self {
+        self.speaker = speaker;
+        self
+    }
+}
+
+/// Who said a blurb, as shown in the backlog.
+#[derive(Debug, Default, Clone, Copy)]
+pub enum Speaker {
+    #[default]
+    Narrator,
+    MainCharacter,
+    Chara(Chara),
+}
+
+impl Speaker {
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            Self::Narrator => None,
+            Self::MainCharacter => Chara::Father.name(),
+            Self::Chara(chara) => chara.name(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CharacterSprite {
+    Still(String),
+    /// A row of frames, whose layout is registered with
+    /// [`AnimationAppExt::register_layout`].
+    Animated {
+        path: &'static str,
+        interval: f32,
+        frames: Range<usize>,
+    },
+}
+
+impl CharacterSprite {
+    /// New character sprite with relative asset path:
+    ///   - character: `textures/characters/`
+    pub fn new(character: impl AsRef<str>) -> Self {
+        Self::Still(format!("textures/characters/{}", character.as_ref()))
+    }
+
+    /// New animated character sprite with the full asset path, since the
+    /// layout is looked up by it.
+    pub fn animated(path: &'static str, interval: f32, frames: Range<usize>) -> Self {
+        Self::Animated {
+            path,
+            interval,
+            frames,
+        }
+    }
+}
+
+/// The portrait being shown, which fades out once it's replaced.
+#[derive(Component)]
+pub struct CharacterSpriteEntity(CharacterSprite);
+
+/// How long portraits take to fade into one another.
+const PORTRAIT_FADE_SECS: f32 = 0.15;
+
+#[derive(Component)]
+struct PortraitFade {
+    timer: Timer,
+    from: f32,
+    to: f32,
+}
+
+impl PortraitFade {
+    fn new(from: f32, to: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(PORTRAIT_FADE_SECS, TimerMode::Once),
+            from,
+            to,
+        }
+    }
+
+    fn alpha(&self) -> f32 {
+        self.from + (self.to - self.from) * self.timer.fraction()
+    }
+}
+
+/// Start a late sprite, like an animated portrait's, where its fade is.
+fn fade_new_sprite(
+    trigger: Trigger<OnAdd, Sprite>,
+    mut portraits: Query<(&PortraitFade, &mut Sprite)>,
+) {
+    if let Ok((fade, mut sprite)) = portraits.get_mut(trigger.target()) {
+        sprite.color.set_alpha(fade.alpha());
+    }
+}
+
+fn textbox_event(
+    mut commands: Commands,
+    mut reader: EventReader<TextboxEvent>,
+    mut sections: ResMut<TextboxSections>,
+    mut finished: EventWriter<TextboxFinished>,
+) {
+    if reader.is_empty() {
+        return;
+    }
+
+    // The same line is often sent twice in a frame, like an interaction's
+    // flavor text alongside an item's pickup text.
+    let mut requests = Vec::<TextboxRequest>::new();
+    for event in reader.read() {
+        let request = TextboxRequest::from(event);
+        match requests
+            .iter_mut()
+            .find(|existing| existing.same_text(&request))
+        {
+            Some(existing) => {
+                existing.ids.extend(request.ids);
+                existing.priority = existing.priority.max(request.priority);
+            }
+            None => requests.push(request),
+        }
+    }
+
+    for request in requests {
+        sections.push(request);
+    }
+
+    let preempted = sections.active.as_ref().is_some_and(|active| {
+        active.priority == TextboxPriority::Flavor
+            && sections
+                .queue
+                .first()
+                .is_some_and(|next| next.priority == TextboxPriority::Cutscene)
+    });
+
+    if preempted {
+        let active = sections.interrupt();
+        finished.write_batch(active.ids.into_iter().map(|id| TextboxFinished {
+            id,
+            interrupted: true,
+        }));
+    }
+
+    if sections.active.is_none() && sections.has_next() {
+        commands.run_system_cached(start_next_request);
+    }
+}
+
+/// Show the next request allowed the textbox, opening it if needed.
+fn start_next_request(
+    mut commands: Commands,
+    mut sections: ResMut<TextboxSections>,
+    player: Option<Single<Entity, With<Player>>>,
+    textbox: Query<(), With<Textbox>>,
+) {
+    if sections.active.is_some() {
+        return;
+    }
+
+    let Some(request) = sections.next_request() else {
+        return;
+    };
+
+    sections.sections = request.blurbs.iter().cloned().rev().collect();
+    sections.active = Some(request);
+
+    if textbox.is_empty() {
+        if let Some(player) = player {
+            commands.entity(*player).trigger(InhibitAddEvent);
+        }
+        commands.run_system_cached(spawn_textbox);
+    }
+    commands.run_system_cached(pop_next_section);
+}
+
+/// One or more identical [`TextboxEvent`]s, waiting for or holding the
+/// textbox.
+struct TextboxRequest {
+    ids: Vec<TextboxId>,
+    blurbs: Vec<TextBlurb>,
+    despawn_when_finished: bool,
+    priority: TextboxPriority,
+    auto: Option<AutoAdvance>,
+}
+
+impl From<&TextboxEvent> for TextboxRequest {
+    fn from(event: &TextboxEvent) -> Self {
+        Self {
+            ids: vec![event.id],
+            blurbs: event.blurbs.clone(),
+            despawn_when_finished: event.despawn_when_finished,
+            priority: event.priority,
+            auto: event.auto,
+        }
+    }
+}
+
+impl TextboxRequest {
+    fn same_text(&self, other: &Self) -> bool {
+        self.despawn_when_finished == other.despawn_when_finished
+            && self
+                .blurbs
+                .iter()
+                .map(|blurb| &blurb.text)
+                .eq(other.blurbs.iter().map(|blurb| &blurb.text))
+    }
+}
+
+#[derive(Default, Resource)]
+pub struct TextboxSections {
+    sections: Vec<TextBlurb>,
+    choices: Vec<String>,
+    speaker: Speaker,
+    /// The request being shown, until its last blurb is dismissed.
+    active: Option<TextboxRequest>,
+    /// Requests waiting for the textbox, highest priority first.
+    queue: Vec<TextboxRequest>,
+    /// Whether a cutscene is holding the textbox open between lines.
+    retained: bool,
+    /// The portrait the current blurb wants shown.
+    portrait: Option<CharacterSprite>,
+}
+
+impl TextboxSections {
+    /// Whether the current blurb has options the player still has to pick from.
+    pub fn awaiting_choice(&self) -> bool {
+        !self.choices.is_empty()
+    }
+
+    fn push(&mut self, request: TextboxRequest) {
+        if request.priority == TextboxPriority::Flavor {
+            let duplicate = self
+                .active
+                .iter_mut()
+                .chain(self.queue.iter_mut())
+                .find(|existing| existing.same_text(&request));
+
+            if let Some(duplicate) = duplicate {
+                duplicate.ids.extend(request.ids);
+                return;
+            }
+        }
+
+        let index = self
+            .queue
+            .iter()
+            .position(|queued| queued.priority < request.priority)
+            .unwrap_or(self.queue.len());
+        self.queue.insert(index, request);
+    }
+
+    /// Whether the showing request doesn't let the player hurry it along.
+    fn ignoring_input(&self) -> bool {
+        self.active
+            .as_ref()
+            .and_then(|request| request.auto)
+            .is_some_and(|auto| auto.ignore_input)
+    }
+
+    /// Whether a queued request can take the textbox once it's free.
+    ///
+    /// While a cutscene holds the textbox, only its own lines can.
+    fn has_next(&self) -> bool {
+        self.queue
+            .first()
+            .is_some_and(|next| !self.retained || next.priority == TextboxPriority::Cutscene)
+    }
+
+    fn next_request(&mut self) -> Option<TextboxRequest> {
+        self.has_next().then(|| self.queue.remove(0))
+    }
+
+    /// Drop the showing request, returning it.
+    fn interrupt(&mut self) -> TextboxRequest {
+        self.sections.clear();
+        self.choices.clear();
+        self.active.take().expect("a request is showing")
+    }
+}
+
+#[derive(InputContext)]
+pub struct TextboxContext;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = bool, require_reset = true)]
+pub struct Interact;
+
+#[derive(Debug, InputAction)]
+#[input_action(output = Vec2, require_reset = true)]
+pub struct ChoiceMove;
+
+/// Open the dialogue backlog over the textbox.
+#[derive(Debug, InputAction)]
+#[input_action(output = bool, require_reset = true)]
+pub struct OpenBacklog;
+
+fn bind(
+    trigger: Trigger<Binding<TextboxContext>>,
+    mut actions: Query<&mut Actions<TextboxContext>>,
+    controls: Res<Controls>,
+) {
+    let mut actions = actions.get_mut(trigger.target()).unwrap();
+
+    actions
+        .bind::<Interact>()
+        .to(controls.inputs(Control::Advance))
+        .with_conditions(JustPress::default());
+
+    actions
+        .bind::<OpenBacklog>()
+        .to(controls.inputs(Control::OpenBacklog))
+        .with_conditions(JustPress::default());
+
+    actions
+        .bind::<ChoiceMove>()
+        .to((
+            Cardinal::wasd_keys(),
+            Cardinal::arrow_keys(),
+            Cardinal::dpad_buttons(),
+        ))
+        .with_conditions(JustPress::default());
+}
+
+/// An event emitted when the user interacts with a textbox awaiting input.
+#[derive(Debug, Event)]
+pub struct TextboxCloseInteraction;
+
+/// An event emitted when the user picks an option from a textbox with choices.
+///
+/// This is always followed by a [`TextboxCloseInteraction`].
+#[derive(Debug, Clone, Copy, Event)]
+pub struct ChoiceSelected(pub usize);
+
+#[derive(Component)]
+struct AwaitInput;
+
+/// Counts down to advancing an [`AutoAdvance`] blurb.
+#[derive(Component)]
+struct AutoAdvanceTimer {
+    timer: Timer,
+    hide_indicator: bool,
+}
+
+fn textbox_input(
+    _: Trigger<Fired<Interact>>,
+    mut commands: Commands,
+    sections: Res<TextboxSections>,
+    textbox: Query<(), With<AwaitInput>>,
+) {
+    if !textbox.is_empty() && !sections.ignoring_input() {
+        commands.run_system_cached(advance);
+    }
+}
+
+fn auto_advance(
+    mut commands: Commands,
+    time: Res<Time>,
+    playback: Res<CutscenePlayback>,
+    textbox: Option<Single<(Entity, &mut AutoAdvanceTimer)>>,
+) {
+    let Some((textbox, mut auto)) = textbox.map(Single::into_inner) else {
+        return;
+    };
+
+    if auto
+        .timer
+        .tick(playback.scale(time.delta()))
+        .just_finished()
+    {
+        commands.entity(textbox).remove::<AutoAdvanceTimer>();
+        commands.run_system_cached(advance);
+    }
+}
+
+/// Move on from the blurb awaiting input.
+fn advance(
+    mut commands: Commands,
+    mut sections: ResMut<TextboxSections>,
+    mut writer: EventWriter<TextboxCloseInteraction>,
+    mut close: EventWriter<TextboxCloseEvent>,
+    mut selected: EventWriter<ChoiceSelected>,
+    mut finished: EventWriter<TextboxFinished>,
+
+    textbox: Single<(Entity, Option<&ChoiceState>), With<AwaitInput>>,
+    text: Single<(Entity, &mut Typewriter), With<TextboxText>>,
+    options: Query<Entity, With<ChoiceOption>>,
+) {
+    let (textbox, choice) = textbox.into_inner();
+
+    let (text, mut typewriter) = text.into_inner();
+    if let Some(page) = typewriter.next_page() {
+        commands.entity(text).insert(page);
+        commands
+            .entity(textbox)
+            .remove::<(AwaitInput, AutoAdvanceTimer)>();
+        return;
+    }
+
+    if let Some(choice) = choice {
+        selected.write(ChoiceSelected(choice.selected));
+        sections.choices.clear();
+        commands.entity(textbox).remove::<ChoiceState>();
+        for option in options.iter() {
+            commands.entity(option).despawn();
+        }
+    }
+
+    match sections.sections.is_empty() {
+        false => {
+            commands.run_system_cached(pop_next_section);
+        }
+        true => {
+            let despawn_when_finished = match sections.active.take() {
+                Some(request) => {
+                    finished.write_batch(request.ids.into_iter().map(|id| TextboxFinished {
+                        id,
+                        interrupted: false,
+                    }));
+                    request.despawn_when_finished
+                }
+                // Dismissing a cutscene line again before the next arrives.
+                None => !sections.retained,
+            };
+
+            if !despawn_when_finished {
+                sections.retained = true;
+            }
+
+            // Keep the textbox open for whatever's waiting rather than
+            // closing and reopening it.
+            if sections.has_next() {
+                commands.run_system_cached(start_next_request);
+            } else if despawn_when_finished {
+                close.write(TextboxCloseEvent);
+            }
+
+            writer.write(TextboxCloseInteraction);
+        }
+    }
+}
+
+#[derive(Event, Default)]
+pub struct TextboxCloseEvent;
+
+fn close_textbox(
+    mut commands: Commands,
+    mut reader: EventReader<TextboxCloseEvent>,
+    mut sections: ResMut<TextboxSections>,
+    mut finished: EventWriter<TextboxFinished>,
+    textbox: Single<Entity, With<Textbox>>,
+    player: Single<Entity, With<Player>>,
+) {
+    if reader.is_empty() {
+        return;
+    }
+    reader.clear();
+
+    commands.entity(*textbox).despawn();
+    commands.entity(*player).trigger(InhibitRemoveEvent);
+
+    sections.retained = false;
+    if sections.active.is_some() {
+        let active = sections.interrupt();
+        finished.write_batch(active.ids.into_iter().map(|id| TextboxFinished {
+            id,
+            interrupted: true,
+        }));
+    }
+
+    // Anything that waited on a cutscene gets a fresh textbox.
+    if sections.has_next() {
+        commands.run_system_cached(start_next_request);
+    }
+}
+
+fn reveal_textbox(
+    _: Trigger<Fired<Interact>>,
+    mut commands: Commands,
+    text: Single<Entity, (With<Typewriter>, With<TextboxText>)>,
+    sections: Res<TextboxSections>,
+) {
+    if sections.ignoring_input() {
+        return;
+    }
+
+    // don't insert `AwaitInput` so that `close_textbox` does not also run,
+    // wait for `TypewriterFinished` to fire!
+    commands.entity(*text).queue(typewriter::reveal_all);
+}
+
+#[derive(Component)]
+#[require(Visibility, Actions<TextboxContext>)]
+pub struct Textbox;
+
+#[derive(Component)]
+struct TextboxText;
+
+pub fn spawn_textbox(server: Res<AssetServer>, mut commands: Commands) {
+    let bounds = Vec2::new(
+        crate::WIDTH * crate::RESOLUTION_SCALE - 80.,
+        crate::HEIGHT * crate::RESOLUTION_SCALE / 3.,
+    );
+    let translation = Vec3::new(0., -60., 0.);
+
+    let textbox = commands
+        .spawn((
+            Textbox,
+            Transform::from_xyz(0., 0., 500.),
+            children![(
+                Sprite::from_image(server.load("textures/textbox.png")),
+                Transform::from_xyz(0., 0., -2.).with_scale(Vec3::splat(crate::RESOLUTION_SCALE)),
+                HIGH_RES_LAYER,
+            )],
+        ))
+        .observe(await_input_visual)
+        .observe(remove_await_input_visual)
+        .id();
+
+    let text = commands
+        .spawn((
+            TextboxText,
+            Text2d::default(),
+            TextFont {
+                font: server.load("fonts/raster-forge.ttf"),
+                font_size: 42.,
+                ..Default::default()
+            },
+            TextBounds::new(bounds.x, bounds.y),
+            Transform::from_translation(translation),
+            Anchor::TopCenter,
+            HIGH_RES_LAYER,
+        ))
+        .observe(glyph_reveal)
+        .observe(finish)
+        .id();
+
+    commands.entity(textbox).add_child(text);
+}
+
+fn handle_sprite_event(
+    _: Single<(), With<Textbox>>,
+    mut events: EventReader<CharacterEvent>,
+    mut expressions: EventReader<ExpressionEvent>,
+    mut reveal: ResMut<GlyphReveal>,
+    mut sections: ResMut<TextboxSections>,
+) {
+    let Some(next_event) = events.read().next() else {
+        return;
+    };
+
+    let expression = expressions.read().last();
+    sections.portrait = match (next_event.speaker, expression) {
+        (Speaker::Chara(chara), Some(expression)) => chara.portrait(&expression.0),
+        _ => next_event.sprite.clone(),
+    };
+
+    reveal.0 = Some(next_event.glyph.clone());
+    sections.speaker = next_event.speaker;
+}
+
+/// Crossfade to the portrait the current blurb wants.
+fn show_portrait(
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    sections: Res<TextboxSections>,
+    textbox: Option<Single<Entity, With<Textbox>>>,
+    current: Query<(Entity, &CharacterSpriteEntity, Option<&Sprite>)>,
+) {
+    let Some(textbox) = textbox else {
+        return;
+    };
+
+    let current = current.iter().next();
+    if current.map(|(_, portrait, _)| &portrait.0) == sections.portrait.as_ref() {
+        return;
+    }
+
+    if let Some((entity, _, sprite)) = current {
+        let alpha = sprite.map_or(1., |sprite| sprite.color.alpha());
+        commands
+            .entity(entity)
+            .remove::<CharacterSpriteEntity>()
+            .insert(PortraitFade::new(alpha, 0.));
+    }
+
+    let Some(portrait) = sections.portrait.clone() else {
+        return;
+    };
+
+    let mut entity = commands.spawn((
+        PortraitFade::new(0., 1.),
+        Transform::from_xyz(0., 0., -3.).with_scale(Vec3::splat(crate::RESOLUTION_SCALE)),
+        HIGH_RES_LAYER,
+        ChildOf(*textbox),
+    ));
+    match &portrait {
+        CharacterSprite::Still(path) => {
+            entity.insert(Sprite {
+                image: server.load(path),
+                color: Color::WHITE.with_alpha(0.),
+                ..Default::default()
+            });
+        }
+        CharacterSprite::Animated {
+            path,
+            interval,
+            frames,
+        } => {
+            entity.insert(AnimationSprite::repeating(*path, *interval, frames.clone()));
+        }
+    }
+    entity.insert(CharacterSpriteEntity(portrait));
+}
+
+fn fade_portraits(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut portraits: Query<(Entity, &mut PortraitFade, Option<&mut Sprite>)>,
+) {
+    for (entity, mut fade, sprite) in portraits.iter_mut() {
+        // Animated portraits only get their sprite once they're set up, so
+        // the fade waits for it. One leaving before then has nothing to fade.
+        let Some(mut sprite) = sprite else {
+            if fade.to == 0. {
+                commands.entity(entity).despawn();
+            }
+            continue;
+        };
+
+        fade.timer.tick(time.delta());
+        sprite.color.set_alpha(fade.alpha());
+
+        if fade.timer.finished() {
+            if fade.to == 0. {
+                commands.entity(entity).despawn();
+            } else {
+                commands.entity(entity).remove::<PortraitFade>();
+            }
+        }
+    }
+}
+
+fn pop_next_section(
+    mut commands: Commands,
+    mut sections: ResMut<TextboxSections>,
+    mut reveal: ResMut<GlyphReveal>,
+    playback: Res<CutscenePlayback>,
+    text: Single<Entity, With<TextboxText>>,
+    textbox: Single<Entity, With<Textbox>>,
+) {
+    let section = sections.sections.pop().unwrap();
+    reveal.0 = Some(section.glyph.clone());
+    sections.choices = section.choices.clone();
+    sections.speaker = section.speaker;
+    sections.portrait = section.character.clone();
+
+    commands.entity(*text).insert(Typewriter::new(
+        Markup::parse(&section.text),
+        playback.speed(),
+    ));
+
+    commands
+        .entity(*textbox)
+        .remove::<(AwaitInput, AutoAdvanceTimer)>();
+}
+
+#[derive(Component)]
+struct AwaitinputVisual;
+
+fn await_input_visual(
+    trigger: Trigger<OnAdd, AwaitInput>,
+    mut commands: Commands,
+    auto: Query<&AutoAdvanceTimer>,
+) {
+    if auto
+        .get(trigger.target())
+        .is_ok_and(|auto| auto.hide_indicator)
+    {
+        return;
+    }
+
+    commands.entity(trigger.target()).with_child((
+        AwaitinputVisual,
+        AnimationSprite::repeating("textures/textbox-await.png", 0.2, 0..4),
+        Transform::from_xyz(0., 0., -1.).with_scale(Vec3::splat(crate::RESOLUTION_SCALE)),
+        HIGH_RES_LAYER,
+    ));
+}
+
+fn remove_await_input_visual(
+    _: Trigger<OnRemove, AwaitInput>,
+    mut commands: Commands,
+    visual: Single<Entity, With<AwaitinputVisual>>,
+) {
+    commands.entity(*visual).despawn();
+}
+
+#[derive(Default, Resource)]
+struct GlyphReveal(Option<Arc<dyn Fn(&mut Commands, &AssetServer) + Send + Sync>>);
+
+fn glyph_reveal(
+    _: Trigger<GlyphRevealed>,
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    reveal: Res<GlyphReveal>,
+) {
+    if let Some(reveal) = &reveal.0 {
+        reveal(&mut commands, &server);
+    }
+}
+
+fn finish(
+    trigger: Trigger<TypewriterFinished>,
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    sections: Res<TextboxSections>,
+    mut history: ResMut<DialogueHistory>,
+    text: Query<&Typewriter>,
+    textbox: Single<Entity, With<Textbox>>,
+) {
+    match sections.active.as_ref().and_then(|request| request.auto) {
+        Some(auto) => {
+            commands.entity(*textbox).insert((
+                AutoAdvanceTimer {
+                    timer: Timer::new(auto.hold, TimerMode::Once),
+                    hide_indicator: auto.hide_indicator,
+                },
+                AwaitInput,
+            ));
+        }
+        None => {
+            commands.entity(*textbox).insert(AwaitInput);
+        }
+    }
+
+    let Ok(text) = text.get(trigger.target()) else {
+        return;
+    };
+    // The backlog and choices wait for the last page.
+    if text.has_next_page() {
+        return;
+    }
+
+    history.push(sections.speaker, text.line());
+    if sections.choices.is_empty() {
+        return;
+    }
+
+    commands
+        .entity(*textbox)
+        .insert(ChoiceState { selected: 0 });
+    for (index, choice) in sections.choices.iter().enumerate() {
+        commands.entity(*textbox).with_child((
+            ChoiceOption(index),
+            Text2d::new(choice.clone()),
+            TextFont {
+                font: server.load("fonts/raster-forge.ttf"),
+                font_size: 42.,
+                ..Default::default()
+            },
+            TextColor(CHOICE_NORMAL),
+            Transform::from_xyz(0., CHOICE_TOP - index as f32 * CHOICE_SPACING, 0.),
+            Anchor::TopCenter,
+            HIGH_RES_LAYER,
+        ));
+    }
+}
+
+const CHOICE_TOP: f32 = -180.;
+const CHOICE_SPACING: f32 = 45.;
+const CHOICE_NORMAL: Color = Color::srgb(0.6, 0.6, 0.6);
+const CHOICE_SELECTED: Color = Color::WHITE;
+
+/// The currently highlighted option of a textbox with choices.
+#[derive(Component)]
+struct ChoiceState {
+    selected: usize,
+}
+
+#[derive(Component)]
+struct ChoiceOption(usize);
+
+fn navigate_choices(
+    trigger: Trigger<Fired<ChoiceMove>>,
+    mut commands: Commands,
+    server: Res<AssetServer>,
+    mut choice: Single<&mut ChoiceState>,
+    options: Query<&ChoiceOption>,
+) {
+    let count = options.iter().count();
+    if count == 0 || trigger.value.y == 0.0 {
+        return;
+    }
+
+    choice.selected = if trigger.value.y > 0.0 {
+        choice.selected.checked_sub(1).unwrap_or(count - 1)
+    } else {
+        (choice.selected + 1) % count
+    };
+
+    commands.spawn(
+        SamplePlayer::new(server.load(glyph_sample("medium.wav")))
+            .with_volume(Volume::Decibels(-6.0)),
+    );
+}
+
+fn highlight_choices(
+    choice: Option<Single<&ChoiceState, Changed<ChoiceState>>>,
+    mut options: Query<(&ChoiceOption, &mut TextColor)>,
+) {
+    let Some(choice) = choice else {
+        return;
+    };
+
+    for (option, mut color) in options.iter_mut() {
+        color.0 = if option.0 == choice.selected {
+            CHOICE_SELECTED
+        } else {
+            CHOICE_NORMAL
+        };
+    }
+}
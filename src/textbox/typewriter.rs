@@ -0,0 +1,550 @@
+//! Feeds a line's [`Markup`] to `bevy_pretty_text`.
+//!
+//! The whole line is laid out from the start, split into text spans by
+//! color, and revealed by a [`TypeWriter`] running at the line's fastest
+//! speed. Slower glyphs and pauses hold the writer back by taking it off the
+//! entity and putting it back, progress and all, once they've passed. Glyphs
+//! with an [`Effect`] are drawn by their own entities over the layout so
+//! they can move on their own.
+//!
+//! Lines too long for their [`TextBounds`] are split into pages at the first
+//! word that doesn't fit, once the layout shows where that is.
+
+use std::ops::Range;
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::text::{TextBounds, TextLayoutInfo};
+use bevy::window::PrimaryWindow;
+use bevy_optix::pixel_perfect::HIGH_RES_LAYER;
+use bevy_pretty_text::prelude::{GlyphRevealed, Reveal, TypeWriter, TypeWriterFinished};
+
+use super::markup::{Effect, Markup};
+
+pub struct TypewriterPlugin;
+
+impl Plugin for TypewriterPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (release_holds, animate_effects).chain())
+            .add_systems(PostUpdate, paginate.after(bevy::text::update_text2d_layout))
+            .add_observer(build_spans)
+            .add_observer(pace_glyphs)
+            .add_observer(finish_line);
+    }
+}
+
+/// Characters revealed per second, unless the markup says otherwise.
+pub const DEFAULT_CPS: f32 = 30.;
+
+const TEXT_COLOR: Color = Color::WHITE;
+
+const SHAKE_AMPLITUDE: f32 = 2.;
+/// How many times a second shaking glyphs jump.
+const SHAKE_RATE: f32 = 20.;
+
+const WAVE_AMPLITUDE: f32 = 4.;
+const WAVE_SPEED: f32 = 6.;
+/// How far along the wave each glyph is from the last.
+const WAVE_SPACING: f32 = 0.6;
+
+/// Reveals this entity's text.
+///
+/// Inserting a new `Typewriter` replaces the line.
+#[derive(Component)]
+#[require(Text2d)]
+pub struct Typewriter {
+    markup: Markup,
+    /// The whole line without its markup, across every page.
+    line: String,
+    /// Scales every glyph's delay, for fast-forwarding.
+    speed: f32,
+    /// How many glyphs the [`TypeWriter`] has revealed.
+    revealed: usize,
+    /// Counts down a pause, holding the [`TypeWriter`] until it's done.
+    hold: Option<Hold>,
+    /// Counts down the line's trailing pause once it's all revealed.
+    trailing: Option<Timer>,
+    finished: bool,
+    /// Glyphs drawn by their own entities, in order.
+    effects: Vec<EffectSlot>,
+    /// How many of `effects` have been spawned.
+    effects_shown: usize,
+    /// The glyphs of each [`TypewriterSpan`], in layout order.
+    spans: Vec<Range<usize>>,
+    /// Whether the line has been checked against its bounds.
+    paginated: bool,
+    /// The rest of the line, once it's been split.
+    overflow: Option<Markup>,
+}
+
+impl Typewriter {
+    pub fn new(markup: Markup, speed: f32) -> Self {
+        Self {
+            line: markup.plain(),
+            markup,
+            speed,
+            revealed: 0,
+            hold: None,
+            trailing: None,
+            finished: false,
+            effects: Vec::new(),
+            effects_shown: 0,
+            spans: Vec::new(),
+            paginated: false,
+            overflow: None,
+        }
+    }
+
+    /// The rest of the line that didn't fit, if any, to be shown next.
+    pub fn next_page(&mut self) -> Option<Self> {
+        let markup = self.overflow.take()?;
+        Some(Self {
+            line: self.line.clone(),
+            ..Self::new(markup, self.speed)
+        })
+    }
+
+    /// Whether more of the line follows this page.
+    pub fn has_next_page(&self) -> bool {
+        self.overflow.is_some()
+    }
+
+    /// The whole line without its markup, across every page.
+    pub fn line(&self) -> &str {
+        &self.line
+    }
+
+    fn text(&self, range: Range<usize>) -> String {
+        self.markup.glyphs[range]
+            .iter()
+            .map(|glyph| glyph.char)
+            .collect()
+    }
+
+    /// The glyph at `byte` into the text of layout span `span`.
+    fn glyph_at(&self, span: usize, byte: usize) -> Option<usize> {
+        // The root's own text comes first in the layout.
+        let range = self.spans.get(span.checked_sub(1)?)?;
+        let text = self.text(range.clone());
+        Some(range.start + text.get(..byte)?.chars().count())
+    }
+
+    fn cps(&self, glyph: usize) -> f32 {
+        self.markup.glyphs[glyph].style.speed.unwrap_or(DEFAULT_CPS)
+    }
+
+    /// The [`TypeWriter`]'s speed: the line's fastest, so every other glyph
+    /// can be reached by holding it back.
+    fn writer_cps(&self) -> f32 {
+        let fastest = (0..self.markup.glyphs.len())
+            .map(|glyph| self.cps(glyph))
+            .fold(DEFAULT_CPS, f32::max);
+        fastest * self.speed
+    }
+
+    /// How much longer than the [`TypeWriter`] takes the markup wants before
+    /// the next visible glyph.
+    fn next_hold(&self) -> f32 {
+        let glyphs = &self.markup.glyphs;
+        let Some(next) = (self.revealed..glyphs.len()).find(|&i| !glyphs[i].char.is_whitespace())
+        else {
+            return 0.;
+        };
+
+        let wanted: f32 = (self.revealed..=next)
+            .map(|i| (glyphs[i].pause + 1. / self.cps(i)) / self.speed)
+            .sum();
+        let typed = (next + 1 - self.revealed) as f32 / self.writer_cps();
+        wanted - typed
+    }
+}
+
+/// Show the rest of the line at once, without holding on its end.
+pub fn reveal_all(mut entity: EntityWorldMut) {
+    let held = entity.get_mut::<Typewriter>().and_then(|mut typewriter| {
+        typewriter.revealed = typewriter.markup.glyphs.len();
+        typewriter.markup.trailing_pause = 0.;
+        if let Some(trailing) = &mut typewriter.trailing {
+            let duration = trailing.duration();
+            trailing.set_elapsed(duration);
+        }
+        typewriter.hold.take()
+    });
+
+    if let Some(held) = held {
+        entity.insert(held.writer);
+    }
+    if let Some(mut reveal) = entity.get_mut::<Reveal>() {
+        reveal.all();
+    }
+}
+
+/// Triggered on a [`Typewriter`] once its whole line is revealed, after
+/// its trailing pause.
+#[derive(Event)]
+pub struct TypewriterFinished;
+
+struct Hold {
+    writer: TypeWriter,
+    timer: Timer,
+}
+
+#[derive(Clone, Copy)]
+struct EffectSlot {
+    glyph: usize,
+    effect: Effect,
+    /// The glyph's span in the [`TextLayoutInfo`], where the root text is 0.
+    span: usize,
+    /// The glyph's offset into its span's text.
+    byte: usize,
+}
+
+#[derive(Component)]
+struct TypewriterSpan;
+
+#[derive(Component)]
+struct EffectGlyph {
+    index: usize,
+    origin: Vec2,
+    effect: Effect,
+}
+
+type SpanParts<'w, 's> = Query<'w, 's, (), Or<(With<TypewriterSpan>, With<EffectGlyph>)>>;
+
+fn build_spans(
+    trigger: Trigger<OnInsert, Typewriter>,
+    mut commands: Commands,
+    mut writers: Query<(&mut Typewriter, &TextFont, Option<&Children>)>,
+    parts: SpanParts,
+) -> Result {
+    let root = trigger.target();
+    let (mut typewriter, font, children) = writers.get_mut(root)?;
+    spawn_spans(&mut commands, root, &mut typewriter, font, children, &parts);
+
+    Ok(())
+}
+
+/// Replace `root`'s text spans with the spans of its line, and start
+/// revealing them from the top.
+fn spawn_spans(
+    commands: &mut Commands,
+    root: Entity,
+    typewriter: &mut Typewriter,
+    font: &TextFont,
+    children: Option<&Children>,
+    parts: &SpanParts,
+) {
+    for child in children.iter().flat_map(|children| children.iter()) {
+        if parts.contains(child) {
+            commands.entity(child).despawn();
+        }
+    }
+
+    let mut effects = Vec::new();
+    let mut spans = Vec::new();
+    for (style, range) in typewriter.markup.spans() {
+        let text = typewriter.text(range.clone());
+
+        // Glyphs with an effect only hold their place in the layout.
+        let color = match style.effect {
+            Some(effect) => {
+                let mut byte = 0;
+                for glyph in range.clone() {
+                    effects.push(EffectSlot {
+                        glyph,
+                        effect,
+                        span: spans.len() + 1,
+                        byte,
+                    });
+                    byte += typewriter.markup.glyphs[glyph].char.len_utf8();
+                }
+                Color::NONE
+            }
+            None => style.color.unwrap_or(TEXT_COLOR),
+        };
+
+        spans.push(range);
+        commands.spawn((
+            TypewriterSpan,
+            TextSpan::new(text),
+            font.clone(),
+            TextColor(color),
+            ChildOf(root),
+        ));
+    }
+
+    typewriter.effects = effects;
+    typewriter.effects_shown = 0;
+    typewriter.spans = spans;
+    typewriter.revealed = 0;
+    typewriter.trailing = None;
+    typewriter.finished = false;
+
+    let writer = TypeWriter::cps(typewriter.writer_cps());
+    let hold = typewriter.next_hold();
+    if hold > 0. {
+        typewriter.hold = Some(Hold {
+            writer,
+            timer: Timer::from_seconds(hold, TimerMode::Once),
+        });
+        commands.entity(root).remove::<TypeWriter>();
+    } else {
+        typewriter.hold = None;
+        commands.entity(root).insert(writer);
+    }
+}
+
+/// Split lines that run past the bottom of their bounds, once they've been
+/// laid out.
+fn paginate(
+    window: Option<Single<&Window, With<PrimaryWindow>>>,
+    mut commands: Commands,
+    mut writers: Query<(
+        Entity,
+        &mut Typewriter,
+        &TextLayoutInfo,
+        &TextBounds,
+        &TextFont,
+        Option<&Children>,
+    )>,
+    parts: SpanParts,
+) {
+    let scale_factor = window.map_or(1., |window| window.scale_factor());
+
+    for (entity, mut typewriter, layout, bounds, font, children) in writers.iter_mut() {
+        if typewriter.paginated {
+            continue;
+        }
+
+        // Wait for the font to load.
+        if layout.glyphs.is_empty() && !typewriter.markup.glyphs.is_empty() {
+            continue;
+        }
+        typewriter.paginated = true;
+
+        let Some(height) = bounds.height else {
+            continue;
+        };
+
+        let first_overflowing = layout
+            .glyphs
+            .iter()
+            .filter(|glyph| (glyph.position.y + glyph.size.y / 2.) / scale_factor > height)
+            .map(|glyph| glyph.line_index)
+            .min();
+        let Some(line) = first_overflowing else {
+            continue;
+        };
+
+        let Some(line_start) = layout
+            .glyphs
+            .iter()
+            .filter(|glyph| glyph.line_index >= line)
+            .filter_map(|glyph| typewriter.glyph_at(glyph.span_index, glyph.byte_index))
+            .min()
+        else {
+            continue;
+        };
+
+        // A word wider than the bounds is broken mid-way, so back up to its
+        // start to keep it whole on the next page.
+        let glyphs = &typewriter.markup.glyphs;
+        let split = (1..=line_start)
+            .rev()
+            .find(|&i| glyphs[i - 1].char.is_whitespace())
+            .unwrap_or(line_start);
+
+        let mut overflow = typewriter.markup.glyphs.split_off(split);
+        if typewriter.markup.glyphs.is_empty() {
+            typewriter.markup.glyphs = overflow;
+            continue;
+        }
+
+        let leading = overflow
+            .iter()
+            .take_while(|glyph| glyph.char.is_whitespace())
+            .count();
+        overflow.drain(..leading);
+        while typewriter
+            .markup
+            .glyphs
+            .last()
+            .is_some_and(|glyph| glyph.char.is_whitespace())
+        {
+            typewriter.markup.glyphs.pop();
+        }
+
+        if !overflow.is_empty() {
+            typewriter.overflow = Some(Markup {
+                glyphs: overflow,
+                trailing_pause: std::mem::take(&mut typewriter.markup.trailing_pause),
+            });
+        }
+        spawn_spans(
+            &mut commands,
+            entity,
+            &mut typewriter,
+            font,
+            children,
+            &parts,
+        );
+    }
+}
+
+/// Hold the [`TypeWriter`] back for pauses and slower glyphs.
+fn pace_glyphs(
+    trigger: Trigger<GlyphRevealed>,
+    mut commands: Commands,
+    mut writers: Query<&mut Typewriter>,
+) {
+    let entity = trigger.target();
+    let Ok(mut typewriter) = writers.get_mut(entity) else {
+        return;
+    };
+
+    // Whitespace is revealed without a trigger, so catch up to this glyph.
+    let glyphs = &typewriter.markup.glyphs;
+    let Some(glyph) =
+        (typewriter.revealed..glyphs.len()).find(|&i| !glyphs[i].char.is_whitespace())
+    else {
+        return;
+    };
+    typewriter.revealed = glyph + 1;
+
+    let hold = typewriter.next_hold();
+    if hold > 0. {
+        commands
+            .entity(entity)
+            .queue(move |mut entity: EntityWorldMut| {
+                let Some(writer) = entity.take::<TypeWriter>() else {
+                    return;
+                };
+                if let Some(mut typewriter) = entity.get_mut::<Typewriter>() {
+                    typewriter.hold = Some(Hold {
+                        writer,
+                        timer: Timer::from_seconds(hold, TimerMode::Once),
+                    });
+                }
+            });
+    }
+}
+
+/// Hold the finished line for its trailing pause.
+fn finish_line(
+    trigger: Trigger<TypeWriterFinished>,
+    mut commands: Commands,
+    mut writers: Query<&mut Typewriter>,
+) {
+    let entity = trigger.target();
+    let Ok(mut typewriter) = writers.get_mut(entity) else {
+        return;
+    };
+
+    typewriter.revealed = typewriter.markup.glyphs.len();
+    let trailing = typewriter.markup.trailing_pause / typewriter.speed;
+    if trailing > 0. {
+        typewriter.trailing = Some(Timer::from_seconds(trailing, TimerMode::Once));
+    } else if !typewriter.finished {
+        typewriter.finished = true;
+        commands.trigger_targets(TypewriterFinished, entity);
+    }
+}
+
+fn release_holds(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut writers: Query<(Entity, &mut Typewriter)>,
+) {
+    for (entity, mut typewriter) in writers.iter_mut() {
+        let typewriter = &mut *typewriter;
+
+        let released = typewriter
+            .hold
+            .as_mut()
+            .is_some_and(|hold| hold.timer.tick(time.delta()).finished());
+        if released {
+            if let Some(hold) = typewriter.hold.take() {
+                commands.entity(entity).insert(hold.writer);
+            }
+        }
+
+        let trailed = typewriter
+            .trailing
+            .as_mut()
+            .is_some_and(|trailing| trailing.tick(time.delta()).finished());
+        if trailed && !typewriter.finished {
+            typewriter.trailing = None;
+            typewriter.finished = true;
+            commands.trigger_targets(TypewriterFinished, entity);
+        }
+    }
+}
+
+fn animate_effects(
+    time: Res<Time>,
+    window: Option<Single<&Window, With<PrimaryWindow>>>,
+    mut commands: Commands,
+    mut writers: Query<(Entity, &mut Typewriter, &TextLayoutInfo, &TextFont, &Anchor)>,
+    mut glyphs: Query<(&EffectGlyph, &mut Transform)>,
+) {
+    // Glyph positions are laid out in physical pixels.
+    let scale_factor = window.map_or(1., |window| window.scale_factor());
+
+    for (entity, mut typewriter, layout, font, anchor) in writers.iter_mut() {
+        let top_left = (Anchor::TopLeft.as_vec() - anchor.as_vec()) * layout.size;
+
+        while let Some(slot) = typewriter.effects.get(typewriter.effects_shown).copied() {
+            if slot.glyph >= typewriter.revealed {
+                break;
+            }
+
+            // The layout catches up a frame after the spans change.
+            let Some(positioned) = layout
+                .glyphs
+                .iter()
+                .find(|glyph| glyph.span_index == slot.span && glyph.byte_index == slot.byte)
+            else {
+                break;
+            };
+
+            let glyph = typewriter.markup.glyphs[slot.glyph];
+            let origin =
+                top_left + Vec2::new(positioned.position.x, -positioned.position.y) / scale_factor;
+            commands.spawn((
+                EffectGlyph {
+                    index: slot.glyph,
+                    origin,
+                    effect: slot.effect,
+                },
+                Text2d::new(glyph.char),
+                font.clone(),
+                TextColor(glyph.style.color.unwrap_or(TEXT_COLOR)),
+                Transform::from_translation(origin.extend(0.)),
+                HIGH_RES_LAYER,
+                ChildOf(entity),
+            ));
+            typewriter.effects_shown += 1;
+        }
+    }
+
+    let t = time.elapsed_secs();
+    for (glyph, mut transform) in glyphs.iter_mut() {
+        let index = glyph.index as f32;
+        let offset = match glyph.effect {
+            Effect::Shake => {
+                let step = (t * SHAKE_RATE).floor();
+                Vec2::new(noise(step + index), noise(step - index)) * SHAKE_AMPLITUDE
+            }
+            Effect::Wave => {
+                Vec2::Y * (t * WAVE_SPEED - index * WAVE_SPACING).sin() * WAVE_AMPLITUDE
+            }
+        };
+
+        transform.translation = (glyph.origin + offset).extend(0.);
+    }
+}
+
+/// A cheap hash of `seed` into `-1.0..1.0`.
+fn noise(seed: f32) -> f32 {
+    ((seed * 12.9898).sin() * 43758.547).fract()
+}
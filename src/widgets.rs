@@ -0,0 +1,150 @@
+use bevy::{
+    input_focus::{InputFocus, directional_navigation::DirectionalNavigationMap},
+    prelude::*,
+};
+
+pub struct WidgetsPlugin;
+
+impl Plugin for WidgetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, highlight_focused_button)
+            .add_observer(focus_on_hover);
+    }
+}
+
+const TEXT_NORMAL: Color = Color::WHITE;
+const TEXT_FOCUSED: Color = Color::BLACK;
+const TEXT_DISABLED: Color = Color::srgb(0.35, 0.35, 0.35);
+
+/// A menu button, highlighted while it has input focus.
+///
+/// Screens put their own action component next to it and handle
+/// `Pointer<Click>` for that.
+#[derive(Default, Component)]
+pub struct UiButton;
+
+/// A button that can't be focused or picked, like Continue without a save.
+#[derive(Component)]
+pub struct Disabled;
+
+/// A button labelled `text`, laid out with `node`.
+pub fn button(
+    text: impl Into<String>,
+    font_size: f32,
+    node: Node,
+    font: &Handle<Font>,
+) -> impl Bundle {
+    (
+        UiButton,
+        Button,
+        Node {
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..node
+        },
+        BackgroundColor::from(Color::NONE),
+        children![(
+            Text::new(text),
+            TextFont {
+                font: font.clone(),
+                font_size,
+                ..Default::default()
+            },
+            TextColor(TEXT_NORMAL),
+        )],
+    )
+}
+
+/// A screen opened over another, like settings over the title screen.
+#[derive(Component)]
+pub struct Overlay {
+    /// Focus to return to once the screen closes.
+    previous_focus: Option<Entity>,
+}
+
+/// A full-screen column drawn over everything below `z_index`.
+pub fn overlay(
+    input_focus: &InputFocus,
+    z_index: i32,
+    background: Color,
+    row_gap: f32,
+) -> impl Bundle {
+    (
+        Overlay {
+            previous_focus: input_focus.0,
+        },
+        GlobalZIndex(z_index),
+        BackgroundColor(background),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(row_gap),
+            ..default()
+        },
+    )
+}
+
+/// Despawn an [`Overlay`], taking its buttons out of navigation and
+/// handing focus back to where it was when the overlay opened.
+pub fn close_overlay(
+    In(overlay): In<Entity>,
+    overlays: Query<&Overlay>,
+    children: Query<&Children>,
+    buttons: Query<(), With<UiButton>>,
+    mut directional_nav_map: ResMut<DirectionalNavigationMap>,
+    mut input_focus: ResMut<InputFocus>,
+    mut commands: Commands,
+) {
+    let Ok(Overlay { previous_focus }) = overlays.get(overlay) else {
+        return;
+    };
+
+    directional_nav_map.remove_multiple(
+        children
+            .iter_descendants(overlay)
+            .filter(|entity| buttons.contains(*entity))
+            .collect(),
+    );
+    commands.entity(overlay).despawn();
+
+    match previous_focus {
+        Some(focus) => input_focus.set(*focus),
+        None => input_focus.clear(),
+    }
+}
+
+fn highlight_focused_button(
+    input_focus: Res<InputFocus>,
+    mut buttons: Query<(Entity, &mut BackgroundColor, &Children, Has<Disabled>), With<UiButton>>,
+    mut text: Query<&mut TextColor>,
+) {
+    for (entity, mut background, children, disabled) in buttons.iter_mut() {
+        let focused = input_focus.0 == Some(entity);
+        background.0 = if focused { TEXT_NORMAL } else { Color::NONE };
+
+        let color = match (disabled, focused) {
+            (true, _) => TEXT_DISABLED,
+            (false, true) => TEXT_FOCUSED,
+            (false, false) => TEXT_NORMAL,
+        };
+        for child in children.iter() {
+            if let Ok(mut text_color) = text.get_mut(child) {
+                text_color.0 = color;
+            }
+        }
+    }
+}
+
+fn focus_on_hover(
+    trigger: Trigger<Pointer<Over>>,
+    buttons: Query<(), (With<UiButton>, Without<Disabled>)>,
+    mut input_focus: ResMut<InputFocus>,
+) {
+    if buttons.contains(trigger.target()) {
+        input_focus.set(trigger.target());
+    }
+}